@@ -0,0 +1,1101 @@
+use crate::{Error, Result};
+
+use goji::{Board, EditIssue, Issue, Jira, SearchOptions, Sprint};
+use serde::Deserialize;
+use serde_json::Value;
+use url::form_urlencoded;
+
+use std::collections::BTreeMap;
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Account {
+    pub account_id: String,
+    pub display_name: String,
+    pub email_address: Option<String>,
+    pub active: bool,
+    pub time_zone: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueLink {
+    #[serde(rename = "type")]
+    pub link_type: LinkType,
+    pub inward_issue: Option<LinkedIssue>,
+    pub outward_issue: Option<LinkedIssue>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct LinkType {
+    pub name: String,
+    pub inward: String,
+    pub outward: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct LinkedIssue {
+    pub key: String,
+    pub fields: LinkedFields,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct LinkedFields {
+    pub summary: Option<String>,
+    pub status: Option<Named>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Named {
+    pub name: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct RemoteLink {
+    pub id: u64,
+    pub object: RemoteObject,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct RemoteObject {
+    pub url: String,
+    pub title: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Author {
+    pub account_id: Option<String>,
+    pub display_name: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Comment {
+    pub author: Option<Author>,
+    pub body: String,
+    pub created: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct History {
+    pub author: Option<Author>,
+    pub created: String,
+    pub items: Vec<HistoryItem>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct HistoryItem {
+    pub field: String,
+    #[serde(rename = "fromString")]
+    pub from: Option<String>,
+    #[serde(rename = "toString")]
+    pub to: Option<String>,
+    /// The raw values behind `from` and `to`, like comma separated sprint IDs.
+    #[serde(rename = "from", default)]
+    pub from_id: Option<String>,
+    #[serde(rename = "to", default)]
+    pub to_id: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Worklog {
+    #[serde(default)]
+    pub id: String,
+    pub author: Option<Author>,
+    pub started: String,
+    pub time_spent_seconds: u64,
+    pub comment: Option<String>,
+}
+
+/// A saved search.
+#[derive(Deserialize, Debug)]
+pub struct Filter {
+    pub id: String,
+    pub name: String,
+    pub jql: Option<String>,
+    pub owner: Option<Author>,
+    #[serde(default)]
+    pub favourite: bool,
+}
+
+#[derive(Deserialize, Debug)]
+struct Comments {
+    comments: Vec<Comment>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Changelog {
+    values: Vec<History>,
+    is_last: bool,
+}
+
+#[derive(Deserialize, Debug)]
+struct Worklogs {
+    worklogs: Vec<Worklog>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Watchers {
+    watchers: Vec<Account>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Project {
+    pub id: String,
+    pub key: String,
+    pub name: String,
+    #[serde(default)]
+    pub issue_types: Vec<ProjectIssueType>,
+    pub lead: Option<Author>,
+    pub project_type_key: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Projects {
+    values: Vec<Project>,
+    is_last: bool,
+}
+
+/// A fix version of a project.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Version {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub released: bool,
+    #[serde(default)]
+    pub archived: bool,
+    pub release_date: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionIssueCounts {
+    pub issues_count: u64,
+    pub issues_unresolved_count: u64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ProjectIssueType {
+    pub id: String,
+    pub name: String,
+    pub subtask: bool,
+}
+
+/// A column of a board, with the statuses mapped to it.
+#[derive(Deserialize, Debug, Clone)]
+pub struct BoardColumn {
+    pub name: String,
+    pub statuses: Vec<StatusRef>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct StatusRef {
+    pub id: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Status {
+    pub id: String,
+    pub name: String,
+}
+
+/// The field a board estimates its issues with, like `timeoriginalestimate`
+/// or the custom field holding story points.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EstimationField {
+    pub field_id: String,
+    pub display_name: String,
+}
+
+/// The columns of a board, how it estimates and the filter selecting its
+/// issues, which Jira returns as a single document.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BoardConfiguration {
+    column_config: ColumnConfig,
+    estimation: Option<Estimation>,
+    filter: Option<FilterRef>,
+}
+
+impl BoardConfiguration {
+    /// The columns in the order they appear on the board.
+    pub fn columns(&self) -> &[BoardColumn] {
+        &self.column_config.columns
+    }
+
+    /// The field the board estimates with, or `None` for issue counts.
+    pub fn estimation(&self) -> Option<&EstimationField> {
+        self.estimation.as_ref().and_then(|v| v.field.as_ref())
+    }
+
+    pub fn filter_id(&self) -> Option<&str> {
+        self.filter.as_ref().map(|v| v.id.as_str())
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct FilterRef {
+    id: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct ColumnConfig {
+    columns: Vec<BoardColumn>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Estimation {
+    field: Option<EstimationField>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Created {
+    key: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AutomationRule {
+    pub id: u64,
+    pub name: String,
+    pub state: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct RuleRun {
+    #[serde(alias = "categoryName")]
+    pub category: String,
+    /// Milliseconds since the epoch.
+    pub created: i64,
+}
+
+#[derive(Deserialize, Debug)]
+struct AuditLog {
+    results: Vec<RuleRun>,
+}
+
+/// All calls the client makes to Jira, so they can be swapped out in tests.
+pub trait JiraApi {
+    fn get_board(&self, board_id: &str) -> Result<Board>;
+
+    fn list_boards(&self) -> Result<Vec<Board>>;
+
+    fn board_configuration(&self, board: &Board) -> Result<BoardConfiguration>;
+
+    fn create_board(&self, name: &str, filter_id: &str, project_key: Option<&str>)
+        -> Result<Board>;
+
+    fn statuses(&self) -> Result<Vec<Status>>;
+
+    fn get_sprint(&self, sprint_id: &str) -> Result<Sprint>;
+
+    fn list_sprints(&self, board: &Board, state: &str) -> Result<Vec<Sprint>>;
+
+    fn sprint_property(&self, sprint_id: &str, key: &str) -> Result<Option<Value>>;
+
+    fn set_sprint_property(&self, sprint_id: &str, key: &str, value: Value) -> Result<()>;
+
+    fn get_issue(&self, issue_key: &str) -> Result<Issue>;
+
+    fn board_issues(&self, board: &Board, jql: &str, fields: &[&str]) -> Result<Vec<Issue>>;
+
+    fn search_issues(&self, jql: &str, fields: &[&str]) -> Result<Vec<Issue>>;
+
+    fn create_issue(&self, fields: BTreeMap<String, Value>) -> Result<String>;
+
+    fn edit_issue(&self, issue_id: &str, fields: BTreeMap<String, Value>) -> Result<()>;
+
+    fn move_to_sprint(&self, sprint_id: &str, issue_keys: &[String]) -> Result<()>;
+
+    fn update_issue(&self, issue_key: &str, update: BTreeMap<String, Vec<Value>>) -> Result<()>;
+
+    fn link_issues(&self, link_type: &str, inward_key: &str, outward_key: &str) -> Result<()>;
+
+    fn remote_links(&self, issue_key: &str) -> Result<Vec<RemoteLink>>;
+
+    fn add_remote_link(&self, issue_key: &str, url: &str, title: &str) -> Result<()>;
+
+    fn comments(&self, issue_key: &str) -> Result<Vec<Comment>>;
+
+    fn changelog(&self, issue_key: &str) -> Result<Vec<History>>;
+
+    fn worklogs(&self, issue_key: &str) -> Result<Vec<Worklog>>;
+
+    fn add_worklog(&self, issue_key: &str, started: &str, seconds: u64) -> Result<()>;
+
+    fn watchers(&self, issue_key: &str) -> Result<Vec<Account>>;
+
+    fn add_watcher(&self, issue_key: &str, account_id: &str) -> Result<()>;
+
+    fn remove_watcher(&self, issue_key: &str, account_id: &str) -> Result<()>;
+
+    fn search_users(&self, query: &str) -> Result<Vec<Account>>;
+
+    fn get_user(&self, account_id: &str) -> Result<Account>;
+
+    fn myself(&self) -> Result<Account>;
+
+    fn get_filter(&self, filter_id: &str) -> Result<Filter>;
+
+    fn my_filters(&self) -> Result<Vec<Filter>>;
+
+    fn create_filter(&self, name: &str, jql: &str) -> Result<Filter>;
+
+    fn get_project(&self, project_key: &str) -> Result<Project>;
+
+    fn list_projects(&self) -> Result<Vec<Project>>;
+
+    fn versions(&self, project_key: &str) -> Result<Vec<Version>>;
+
+    fn version_issue_counts(&self, version_id: &str) -> Result<VersionIssueCounts>;
+
+    fn create_version(
+        &self,
+        project_id: &str,
+        name: &str,
+        release_date: Option<&str>,
+    ) -> Result<Version>;
+
+    fn release_version(&self, version_id: &str, release_date: &str) -> Result<()>;
+
+    fn automation_rules(&self, project_id: &str) -> Result<Vec<AutomationRule>>;
+
+    fn last_rule_run(&self, rule_id: u64) -> Result<Option<RuleRun>>;
+}
+
+impl JiraApi for Jira {
+    fn get_board(&self, board_id: &str) -> Result<Board> {
+        Ok(self.boards().get(board_id)?)
+    }
+
+    fn list_boards(&self) -> Result<Vec<Board>> {
+        Ok(self.boards().iter(&Default::default())?.collect())
+    }
+
+    fn board_configuration(&self, board: &Board) -> Result<BoardConfiguration> {
+        let endpoint = format!("/board/{}/configuration", board.id);
+        Ok(self.get("agile", &endpoint)?)
+    }
+
+    fn create_board(
+        &self,
+        name: &str,
+        filter_id: &str,
+        project_key: Option<&str>,
+    ) -> Result<Board> {
+        let filter_id = filter_id
+            .parse::<u64>()
+            .map_err(|_| Error::Config("filter-id".to_owned()))?;
+        let mut body = serde_json::json!({
+            "name": name,
+            "type": "scrum",
+            "filterId": filter_id,
+        });
+        if let Some(project_key) = project_key {
+            body["location"] = serde_json::json!({
+                "type": "project",
+                "projectKeyOrId": project_key,
+            });
+        }
+        Ok(self.post("agile", "/board", body)?)
+    }
+
+    fn statuses(&self) -> Result<Vec<Status>> {
+        Ok(self.get("api", "/status")?)
+    }
+
+    fn get_sprint(&self, sprint_id: &str) -> Result<Sprint> {
+        Ok(self.sprints().get(sprint_id)?)
+    }
+
+    fn list_sprints(&self, board: &Board, state: &str) -> Result<Vec<Sprint>> {
+        let search = SearchOptions::builder().state(state).build();
+        Ok(self.sprints().iter(board, &search)?.collect())
+    }
+
+    fn sprint_property(&self, sprint_id: &str, key: &str) -> Result<Option<Value>> {
+        let endpoint = format!("/sprint/{}/properties/{}", sprint_id, key);
+        match self.get::<Value>("agile", &endpoint).map_err(Error::from) {
+            Ok(mut property) => Ok(Some(property["value"].take())),
+            Err(Error::NotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn set_sprint_property(&self, sprint_id: &str, key: &str, value: Value) -> Result<()> {
+        let endpoint = format!("/sprint/{}/properties/{}", sprint_id, key);
+        self.put::<Value, _>("agile", &endpoint, value)?;
+        Ok(())
+    }
+
+    fn get_issue(&self, issue_key: &str) -> Result<Issue> {
+        Ok(self.issues().get(issue_key)?)
+    }
+
+    fn board_issues(&self, board: &Board, jql: &str, fields: &[&str]) -> Result<Vec<Issue>> {
+        let search = SearchOptions::builder()
+            .fields(fields.to_vec())
+            .jql(jql)
+            .build();
+        Ok(self
+            .issues()
+            .iter(board, &search)
+            .map_err(Error::jql)?
+            .collect())
+    }
+
+    fn search_issues(&self, jql: &str, fields: &[&str]) -> Result<Vec<Issue>> {
+        let search = SearchOptions::builder().fields(fields.to_vec()).build();
+        Ok(self
+            .search()
+            .iter(jql, &search)
+            .map_err(Error::jql)?
+            .collect())
+    }
+
+    fn create_issue(&self, fields: BTreeMap<String, Value>) -> Result<String> {
+        let body = serde_json::json!({ "fields": fields });
+        let created: Created = self.post("api", "/issue", body)?;
+        Ok(created.key)
+    }
+
+    fn edit_issue(&self, issue_id: &str, fields: BTreeMap<String, Value>) -> Result<()> {
+        Ok(self.issues().edit(issue_id, EditIssue { fields })?)
+    }
+
+    fn move_to_sprint(&self, sprint_id: &str, issue_keys: &[String]) -> Result<()> {
+        let body = serde_json::json!({ "issues": issue_keys });
+        self.post::<Value, _>("agile", &format!("/sprint/{}/issue", sprint_id), body)?;
+        Ok(())
+    }
+
+    fn update_issue(&self, issue_key: &str, update: BTreeMap<String, Vec<Value>>) -> Result<()> {
+        let body = serde_json::json!({ "update": update });
+        self.put::<Value, _>("api", &format!("/issue/{}", issue_key), body)?;
+        Ok(())
+    }
+
+    fn link_issues(&self, link_type: &str, inward_key: &str, outward_key: &str) -> Result<()> {
+        let body = serde_json::json!({
+            "type": { "name": link_type },
+            "inwardIssue": { "key": inward_key },
+            "outwardIssue": { "key": outward_key },
+        });
+        self.post::<Value, _>("api", "/issueLink", body)?;
+        Ok(())
+    }
+
+    fn remote_links(&self, issue_key: &str) -> Result<Vec<RemoteLink>> {
+        Ok(self.get("api", &format!("/issue/{}/remotelink", issue_key))?)
+    }
+
+    fn add_remote_link(&self, issue_key: &str, url: &str, title: &str) -> Result<()> {
+        let body = serde_json::json!({ "object": { "url": url, "title": title } });
+        self.post::<Value, _>("api", &format!("/issue/{}/remotelink", issue_key), body)?;
+        Ok(())
+    }
+
+    fn comments(&self, issue_key: &str) -> Result<Vec<Comment>> {
+        let comments: Comments = self.get("api", &format!("/issue/{}/comment", issue_key))?;
+        Ok(comments.comments)
+    }
+
+    fn changelog(&self, issue_key: &str) -> Result<Vec<History>> {
+        let mut changes = Vec::new();
+        loop {
+            let endpoint = format!("/issue/{}/changelog?startAt={}", issue_key, changes.len());
+            let page: Changelog = self.get("api", &endpoint)?;
+            let done = page.is_last || page.values.is_empty();
+            changes.extend(page.values);
+            if done {
+                return Ok(changes);
+            }
+        }
+    }
+
+    fn worklogs(&self, issue_key: &str) -> Result<Vec<Worklog>> {
+        let worklogs: Worklogs = self.get("api", &format!("/issue/{}/worklog", issue_key))?;
+        Ok(worklogs.worklogs)
+    }
+
+    fn add_worklog(&self, issue_key: &str, started: &str, seconds: u64) -> Result<()> {
+        let body = serde_json::json!({ "started": started, "timeSpentSeconds": seconds });
+        self.post::<Value, _>("api", &format!("/issue/{}/worklog", issue_key), body)?;
+        Ok(())
+    }
+
+    fn watchers(&self, issue_key: &str) -> Result<Vec<Account>> {
+        let watchers: Watchers = self.get("api", &format!("/issue/{}/watchers", issue_key))?;
+        Ok(watchers.watchers)
+    }
+
+    fn add_watcher(&self, issue_key: &str, account_id: &str) -> Result<()> {
+        // The body is the account ID as a plain JSON string.
+        let endpoint = format!("/issue/{}/watchers", issue_key);
+        self.post::<Value, _>("api", &endpoint, account_id)?;
+        Ok(())
+    }
+
+    fn remove_watcher(&self, issue_key: &str, account_id: &str) -> Result<()> {
+        let query = form_urlencoded::Serializer::new(String::new())
+            .append_pair("accountId", account_id)
+            .finish();
+        let endpoint = format!("/issue/{}/watchers?{}", issue_key, query);
+        self.delete::<Value>("api", &endpoint)?;
+        Ok(())
+    }
+
+    fn search_users(&self, query: &str) -> Result<Vec<Account>> {
+        let query = form_urlencoded::Serializer::new(String::new())
+            .append_pair("query", query)
+            .finish();
+        Ok(self.get("api", &format!("/user/search?{}", query))?)
+    }
+
+    fn get_user(&self, account_id: &str) -> Result<Account> {
+        let query = form_urlencoded::Serializer::new(String::new())
+            .append_pair("accountId", account_id)
+            .finish();
+        Ok(self.get("api", &format!("/user?{}", query))?)
+    }
+
+    fn myself(&self) -> Result<Account> {
+        Ok(self.get("api", "/myself")?)
+    }
+
+    fn get_filter(&self, filter_id: &str) -> Result<Filter> {
+        Ok(self.get("api", &format!("/filter/{}", filter_id))?)
+    }
+
+    /// Returns the filters owned by the user and the filters they marked as favourite.
+    fn my_filters(&self) -> Result<Vec<Filter>> {
+        Ok(self.get("api", "/filter/my?includeFavourites=true")?)
+    }
+
+    fn create_filter(&self, name: &str, jql: &str) -> Result<Filter> {
+        let body = serde_json::json!({ "name": name, "jql": jql });
+        Ok(self.post("api", "/filter", body)?)
+    }
+
+    fn get_project(&self, project_key: &str) -> Result<Project> {
+        Ok(self.get("api", &format!("/project/{}", project_key))?)
+    }
+
+    fn list_projects(&self) -> Result<Vec<Project>> {
+        let mut projects = Vec::new();
+        loop {
+            let endpoint = format!("/project/search?expand=lead&startAt={}", projects.len());
+            let page: Projects = self.get("api", &endpoint)?;
+            let done = page.is_last || page.values.is_empty();
+            projects.extend(page.values);
+            if done {
+                return Ok(projects);
+            }
+        }
+    }
+
+    fn versions(&self, project_key: &str) -> Result<Vec<Version>> {
+        Ok(self.get("api", &format!("/project/{}/versions", project_key))?)
+    }
+
+    fn version_issue_counts(&self, version_id: &str) -> Result<VersionIssueCounts> {
+        let endpoint = format!("/version/{}/unresolvedIssueCount", version_id);
+        Ok(self.get("api", &endpoint)?)
+    }
+
+    fn create_version(
+        &self,
+        project_id: &str,
+        name: &str,
+        release_date: Option<&str>,
+    ) -> Result<Version> {
+        let body = serde_json::json!({
+            "projectId": project_id.parse::<u64>().unwrap_or_default(),
+            "name": name,
+            "releaseDate": release_date,
+        });
+        Ok(self.post("api", "/version", body)?)
+    }
+
+    fn release_version(&self, version_id: &str, release_date: &str) -> Result<()> {
+        let body = serde_json::json!({ "released": true, "releaseDate": release_date });
+        self.put::<Value, _>("api", &format!("/version/{}", version_id), body)?;
+        Ok(())
+    }
+
+    fn automation_rules(&self, project_id: &str) -> Result<Vec<AutomationRule>> {
+        Ok(self.get("cb-automation", &format!("/project/{}/rule", project_id))?)
+    }
+
+    fn last_rule_run(&self, rule_id: u64) -> Result<Option<RuleRun>> {
+        let log: AuditLog = self.get(
+            "cb-automation",
+            &format!("/audit/GLOBAL/item?ruleIdFilter={}&limit=1", rule_id),
+        )?;
+        Ok(log.results.into_iter().next())
+    }
+}
+
+#[cfg(test)]
+pub mod fake {
+    use super::{
+        Account, AutomationRule, BoardConfiguration, ColumnConfig, Comment, Estimation, Filter,
+        FilterRef, History, JiraApi, Project, RemoteLink, RuleRun, Status, Version,
+        VersionIssueCounts, Worklog,
+    };
+    use crate::{Error, Result};
+
+    use goji::{Board, Issue, Sprint};
+    use serde_json::Value;
+
+    use std::cell::{Cell, RefCell};
+    use std::collections::BTreeMap;
+    use std::rc::Rc;
+
+    /// A fixture based implementation of `JiraApi` which records the queries and edits it gets.
+    #[derive(Default)]
+    pub struct Fake {
+        boards: Vec<Value>,
+        columns: Vec<Value>,
+        estimation: Option<Value>,
+        statuses: Vec<Value>,
+        sprints: Vec<Value>,
+        issues: Vec<Value>,
+        users: Vec<Value>,
+        projects: Vec<Value>,
+        filters: Vec<Value>,
+        versions: Vec<Value>,
+        activity: Vec<(&'static str, Value)>,
+        read_only: bool,
+        /// The number of board configurations fetched.
+        pub configurations: Rc<Cell<usize>>,
+        pub queries: Rc<RefCell<Vec<String>>>,
+        pub edits: Changes<Value>,
+        pub updates: Changes<Vec<Value>>,
+        pub remote_links: Calls<Value>,
+        pub created: Rc<RefCell<Vec<BTreeMap<String, Value>>>>,
+        pub moves: Calls<Vec<String>>,
+        pub properties: Rc<RefCell<BTreeMap<String, Value>>>,
+        pub worklogs: Calls<u64>,
+        pub watchers: Calls<String>,
+    }
+
+    /// Calls made to the fake, by the issue or sprint they were made for.
+    type Calls<T> = Rc<RefCell<Vec<(String, T)>>>;
+
+    type Changes<T> = Calls<BTreeMap<String, T>>;
+
+    impl Fake {
+        pub fn new() -> Self {
+            Default::default()
+        }
+
+        pub fn board(mut self, board: Value) -> Self {
+            self.boards.push(board);
+            self
+        }
+
+        /// Adds a column, which is used for every board.
+        pub fn column(mut self, column: Value) -> Self {
+            self.columns.push(column);
+            self
+        }
+
+        /// Sets the estimation field, which is used for every board.
+        pub fn estimation(mut self, field: Value) -> Self {
+            self.estimation = Some(field);
+            self
+        }
+
+        pub fn status(mut self, status: Value) -> Self {
+            self.statuses.push(status);
+            self
+        }
+
+        pub fn sprint(mut self, sprint: Value) -> Self {
+            self.sprints.push(sprint);
+            self
+        }
+
+        pub fn issue(mut self, issue: Value) -> Self {
+            self.issues.push(issue);
+            self
+        }
+
+        /// Adds a user account, the first one is the logged in user.
+        pub fn user(mut self, user: Value) -> Self {
+            self.users.push(user);
+            self
+        }
+
+        pub fn project(mut self, project: Value) -> Self {
+            self.projects.push(project);
+            self
+        }
+
+        pub fn filter(mut self, filter: Value) -> Self {
+            self.filters.push(filter);
+            self
+        }
+
+        /// Adds a version, which is used for every project. The issue counts
+        /// of the version are read from the same object.
+        pub fn version(mut self, version: Value) -> Self {
+            self.versions.push(version);
+            self
+        }
+
+        /// Adds a comment, changelog entry or worklog, which are returned
+        /// for every issue.
+        pub fn activity(mut self, kind: &'static str, activity: Value) -> Self {
+            self.activity.push((kind, activity));
+            self
+        }
+
+        /// Rejects edits, like Jira does without the permission to edit.
+        pub fn read_only(mut self) -> Self {
+            self.read_only = true;
+            self
+        }
+
+        fn activities<T: serde::de::DeserializeOwned>(&self, kind: &str) -> Vec<T> {
+            self.activity
+                .iter()
+                .filter(|(v, _)| *v == kind)
+                .map(|(_, v)| serde_json::from_value(v.clone()).unwrap())
+                .collect()
+        }
+
+        fn find<T: serde::de::DeserializeOwned>(&self, items: &[Value], id: &str) -> Result<T> {
+            items
+                .iter()
+                .find(|v| v["id"].to_string().trim_matches('"') == id || v["key"] == id)
+                .map(|v| serde_json::from_value(v.clone()).unwrap())
+                .ok_or(Error::NotFound(id.to_owned()))
+        }
+
+        fn all<T: serde::de::DeserializeOwned>(&self, items: &[Value]) -> Vec<T> {
+            items
+                .iter()
+                .map(|v| serde_json::from_value(v.clone()).unwrap())
+                .collect()
+        }
+    }
+
+    impl JiraApi for Fake {
+        fn get_board(&self, board_id: &str) -> Result<Board> {
+            self.find(&self.boards, board_id)
+        }
+
+        fn list_boards(&self) -> Result<Vec<Board>> {
+            Ok(self.all(&self.boards))
+        }
+
+        /// Uses the columns, the estimation field and the first filter for
+        /// every board.
+        fn board_configuration(&self, _board: &Board) -> Result<BoardConfiguration> {
+            self.configurations.set(self.configurations.get() + 1);
+            Ok(BoardConfiguration {
+                column_config: ColumnConfig {
+                    columns: self.all(&self.columns),
+                },
+                estimation: Some(Estimation {
+                    field: self
+                        .estimation
+                        .as_ref()
+                        .map(|v| serde_json::from_value(v.clone()).unwrap()),
+                }),
+                filter: self.filters.first().map(|v| FilterRef {
+                    id: v["id"].as_str().unwrap_or_default().to_owned(),
+                }),
+            })
+        }
+
+        fn create_board(
+            &self,
+            name: &str,
+            filter_id: &str,
+            project_key: Option<&str>,
+        ) -> Result<Board> {
+            let mut created = self.created.borrow_mut();
+            let mut fields = BTreeMap::new();
+            fields.insert("name".to_owned(), Value::from(name));
+            fields.insert("filterId".to_owned(), Value::from(filter_id));
+            fields.insert("location".to_owned(), Value::from(project_key));
+            created.push(fields);
+            Ok(serde_json::from_value(serde_json::json!({
+                "id": created.len(),
+                "self": "",
+                "name": name,
+                "type": "scrum",
+            }))
+            .unwrap())
+        }
+
+        fn statuses(&self) -> Result<Vec<Status>> {
+            Ok(self.all(&self.statuses))
+        }
+
+        fn get_sprint(&self, sprint_id: &str) -> Result<Sprint> {
+            self.find(&self.sprints, sprint_id)
+        }
+
+        fn list_sprints(&self, board: &Board, state: &str) -> Result<Vec<Sprint>> {
+            Ok(self
+                .all::<Sprint>(&self.sprints)
+                .into_iter()
+                .filter(|v| v.origin_board_id == Some(board.id))
+                .filter(|v| {
+                    state.is_empty()
+                        || state
+                            .split(',')
+                            .any(|state| v.state.as_deref() == Some(state))
+                })
+                .collect())
+        }
+
+        fn sprint_property(&self, sprint_id: &str, key: &str) -> Result<Option<Value>> {
+            let key = format!("{}/{}", sprint_id, key);
+            Ok(self.properties.borrow().get(&key).cloned())
+        }
+
+        fn set_sprint_property(&self, sprint_id: &str, key: &str, value: Value) -> Result<()> {
+            let key = format!("{}/{}", sprint_id, key);
+            self.properties.borrow_mut().insert(key, value);
+            Ok(())
+        }
+
+        fn get_issue(&self, issue_key: &str) -> Result<Issue> {
+            self.find(&self.issues, issue_key)
+        }
+
+        fn board_issues(&self, _board: &Board, jql: &str, _fields: &[&str]) -> Result<Vec<Issue>> {
+            self.queries.borrow_mut().push(jql.to_owned());
+            Ok(self.all(&self.issues))
+        }
+
+        fn search_issues(&self, jql: &str, _fields: &[&str]) -> Result<Vec<Issue>> {
+            self.queries.borrow_mut().push(jql.to_owned());
+            Ok(self.all(&self.issues))
+        }
+
+        fn create_issue(&self, fields: BTreeMap<String, Value>) -> Result<String> {
+            let mut created = self.created.borrow_mut();
+            created.push(fields);
+            Ok(format!("NEW-{}", created.len()))
+        }
+
+        fn edit_issue(&self, issue_id: &str, fields: BTreeMap<String, Value>) -> Result<()> {
+            if self.read_only {
+                return Err(Error::Forbidden(format!("unable to edit {}", issue_id)));
+            }
+            self.edits.borrow_mut().push((issue_id.to_owned(), fields));
+            Ok(())
+        }
+
+        fn move_to_sprint(&self, sprint_id: &str, issue_keys: &[String]) -> Result<()> {
+            self.moves
+                .borrow_mut()
+                .push((sprint_id.to_owned(), issue_keys.to_vec()));
+            Ok(())
+        }
+
+        fn update_issue(
+            &self,
+            issue_key: &str,
+            update: BTreeMap<String, Vec<Value>>,
+        ) -> Result<()> {
+            self.updates
+                .borrow_mut()
+                .push((issue_key.to_owned(), update));
+            Ok(())
+        }
+
+        /// Records a link as an edit of the inward issue, keyed by the link type.
+        fn link_issues(&self, link_type: &str, inward_key: &str, outward_key: &str) -> Result<()> {
+            let mut fields = BTreeMap::new();
+            fields.insert(link_type.to_owned(), Value::from(outward_key));
+            self.edits
+                .borrow_mut()
+                .push((inward_key.to_owned(), fields));
+            Ok(())
+        }
+
+        fn remote_links(&self, issue_key: &str) -> Result<Vec<RemoteLink>> {
+            Ok(self
+                .remote_links
+                .borrow()
+                .iter()
+                .filter(|(key, _)| key == issue_key)
+                .map(|(_, link)| serde_json::from_value(link.clone()).unwrap())
+                .collect())
+        }
+
+        fn add_remote_link(&self, issue_key: &str, url: &str, title: &str) -> Result<()> {
+            let id = self.remote_links.borrow().len() + 1;
+            self.remote_links.borrow_mut().push((
+                issue_key.to_owned(),
+                serde_json::json!({ "id": id, "object": { "url": url, "title": title } }),
+            ));
+            Ok(())
+        }
+
+        fn comments(&self, _issue_key: &str) -> Result<Vec<Comment>> {
+            Ok(self.activities("comment"))
+        }
+
+        fn changelog(&self, _issue_key: &str) -> Result<Vec<History>> {
+            Ok(self.activities("change"))
+        }
+
+        fn worklogs(&self, _issue_key: &str) -> Result<Vec<Worklog>> {
+            Ok(self.activities("worklog"))
+        }
+
+        fn add_worklog(&self, issue_key: &str, _started: &str, seconds: u64) -> Result<()> {
+            self.worklogs
+                .borrow_mut()
+                .push((issue_key.to_owned(), seconds));
+            Ok(())
+        }
+
+        fn watchers(&self, issue_key: &str) -> Result<Vec<Account>> {
+            let watchers = self.watchers.borrow();
+            Ok(self
+                .all::<Account>(&self.users)
+                .into_iter()
+                .filter(|v| watchers.contains(&(issue_key.to_owned(), v.account_id.clone())))
+                .collect())
+        }
+
+        fn add_watcher(&self, issue_key: &str, account_id: &str) -> Result<()> {
+            self.watchers
+                .borrow_mut()
+                .push((issue_key.to_owned(), account_id.to_owned()));
+            Ok(())
+        }
+
+        fn remove_watcher(&self, issue_key: &str, account_id: &str) -> Result<()> {
+            self.watchers
+                .borrow_mut()
+                .retain(|(key, id)| key != issue_key || id != account_id);
+            Ok(())
+        }
+
+        fn search_users(&self, query: &str) -> Result<Vec<Account>> {
+            let query = query.to_lowercase();
+            Ok(self
+                .all::<Account>(&self.users)
+                .into_iter()
+                .filter(|v| {
+                    v.display_name.to_lowercase().contains(&query)
+                        || v.email_address
+                            .as_deref()
+                            .map(|v| v.to_lowercase().contains(&query))
+                            .unwrap_or(false)
+                })
+                .collect())
+        }
+
+        fn get_user(&self, account_id: &str) -> Result<Account> {
+            self.all::<Account>(&self.users)
+                .into_iter()
+                .find(|v| v.account_id == account_id)
+                .ok_or(Error::NotFound(account_id.to_owned()))
+        }
+
+        fn myself(&self) -> Result<Account> {
+            self.all(&self.users)
+                .into_iter()
+                .next()
+                .ok_or(Error::NotFound("myself".to_owned()))
+        }
+
+        fn get_filter(&self, filter_id: &str) -> Result<Filter> {
+            self.all(&self.filters)
+                .into_iter()
+                .find(|v: &Filter| v.id == filter_id)
+                .ok_or(Error::NotFound(format!("filter {}", filter_id)))
+        }
+
+        fn my_filters(&self) -> Result<Vec<Filter>> {
+            Ok(self.all(&self.filters))
+        }
+
+        fn create_filter(&self, name: &str, jql: &str) -> Result<Filter> {
+            let mut created = self.created.borrow_mut();
+            let mut fields = BTreeMap::new();
+            fields.insert("name".to_owned(), Value::from(name));
+            fields.insert("jql".to_owned(), Value::from(jql));
+            created.push(fields);
+            Ok(Filter {
+                id: format!("{}", created.len()),
+                name: name.to_owned(),
+                jql: Some(jql.to_owned()),
+                owner: None,
+                favourite: false,
+            })
+        }
+
+        fn get_project(&self, project_key: &str) -> Result<Project> {
+            self.find(&self.projects, project_key)
+        }
+
+        fn list_projects(&self) -> Result<Vec<Project>> {
+            Ok(self.all(&self.projects))
+        }
+
+        fn versions(&self, _project_key: &str) -> Result<Vec<Version>> {
+            Ok(self.all(&self.versions))
+        }
+
+        fn version_issue_counts(&self, version_id: &str) -> Result<VersionIssueCounts> {
+            self.find(&self.versions, version_id)
+        }
+
+        fn create_version(
+            &self,
+            project_id: &str,
+            name: &str,
+            release_date: Option<&str>,
+        ) -> Result<Version> {
+            let mut created = self.created.borrow_mut();
+            let mut fields = BTreeMap::new();
+            fields.insert("projectId".to_owned(), Value::from(project_id));
+            fields.insert("name".to_owned(), Value::from(name));
+            fields.insert("releaseDate".to_owned(), Value::from(release_date));
+            created.push(fields);
+            Ok(Version {
+                id: format!("{}", created.len()),
+                name: name.to_owned(),
+                released: false,
+                archived: false,
+                release_date: release_date.map(|v| v.to_owned()),
+            })
+        }
+
+        fn release_version(&self, version_id: &str, release_date: &str) -> Result<()> {
+            let mut fields = BTreeMap::new();
+            fields.insert("released".to_owned(), Value::from(true));
+            fields.insert("releaseDate".to_owned(), Value::from(release_date));
+            self.edits
+                .borrow_mut()
+                .push((version_id.to_owned(), fields));
+            Ok(())
+        }
+
+        fn automation_rules(&self, _project_id: &str) -> Result<Vec<AutomationRule>> {
+            Ok(Vec::new())
+        }
+
+        fn last_rule_run(&self, _rule_id: u64) -> Result<Option<RuleRun>> {
+            Ok(None)
+        }
+    }
+}
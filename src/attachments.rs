@@ -0,0 +1,107 @@
+use crate::api::Author;
+use crate::{Error, Result};
+
+use reqwest::blocking::{multipart, Client, RequestBuilder, Response};
+use serde::Deserialize;
+use serde_json::Value;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// An attachment as found in the `attachment` field of an issue.
+#[derive(Deserialize, Debug)]
+pub struct Attachment {
+    pub id: String,
+    pub filename: String,
+    #[serde(default)]
+    pub size: u64,
+    pub created: String,
+    pub author: Option<Author>,
+    /// URL of the file itself.
+    pub content: String,
+}
+
+/// Uploads and downloads attachments, which goji has no support for as the
+/// uploads are multipart requests and the downloads aren't JSON.
+pub struct Attachments {
+    url: String,
+    user: String,
+    token: String,
+    client: Client,
+}
+
+impl Attachments {
+    pub fn new(url: &str, user: &str, token: &str, client: Client) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_owned(),
+            user: user.to_owned(),
+            token: token.to_owned(),
+            client,
+        }
+    }
+
+    /// Attaches a file to an issue.
+    pub fn upload(&self, issue_key: &str, path: &Path) -> Result<Vec<Attachment>> {
+        let form = multipart::Form::new().file("file", path)?;
+        let request = self
+            .client
+            .post(&format!(
+                "{}/rest/api/3/issue/{}/attachments",
+                self.url, issue_key
+            ))
+            // Jira rejects multipart requests without this header (XSRF check).
+            .header("X-Atlassian-Token", "no-check")
+            .multipart(form);
+
+        self.send(request)?
+            .json()
+            .map_err(|err| Error::Attachment(err.to_string()))
+    }
+
+    /// Downloads an attachment into a directory and returns the path it was
+    /// written to.
+    pub fn download(&self, attachment: &Attachment, dir: &Path) -> Result<PathBuf> {
+        let bytes = self
+            .send(self.client.get(&attachment.content))?
+            .bytes()
+            .map_err(|err| Error::Attachment(err.to_string()))?;
+
+        // Only keep the file name, so attachments can't be written outside `dir`.
+        let name = Path::new(&attachment.filename)
+            .file_name()
+            .map(|v| v.to_owned())
+            .unwrap_or_else(|| attachment.id.clone().into());
+        let path = dir.join(name);
+        fs::create_dir_all(dir)?;
+        fs::write(&path, &bytes)?;
+        Ok(path)
+    }
+
+    fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let response = request
+            .basic_auth(&self.user, Some(&self.token))
+            .send()
+            .map_err(|err| Error::Attachment(err.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body: Value = response.json().unwrap_or_default();
+            let message = body["errorMessage"]
+                .as_str()
+                .or_else(|| body["errorMessages"][0].as_str())
+                .unwrap_or_else(|| status.as_str());
+            return Err(Error::Attachment(message.to_owned()));
+        }
+
+        Ok(response)
+    }
+}
+
+/// Formats a file size, e.g. `12.3 KB`.
+pub fn size(bytes: u64) -> String {
+    match bytes {
+        b if b < 1024 => format!("{} B", b),
+        b if b < 1024 * 1024 => format!("{:.1} KB", b as f64 / 1024.0),
+        b => format!("{:.1} MB", b as f64 / 1024.0 / 1024.0),
+    }
+}
@@ -0,0 +1,26 @@
+use crate::{Error, Result};
+
+use std::process::Command;
+
+/// Opens a URL in the default browser.
+pub fn open(url: &str) -> Result<()> {
+    let mut command = match () {
+        _ if cfg!(target_os = "macos") => Command::new("open"),
+        _ if cfg!(windows) => {
+            // The empty argument is the window title `start` expects first.
+            let mut command = Command::new("cmd");
+            command.args(["/C", "start", ""]);
+            command
+        }
+        _ => Command::new("xdg-open"),
+    };
+
+    match command.arg(url).status() {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => Err(Error::Browser(format!(
+            "the browser exited with {}",
+            status
+        ))),
+        Err(err) => Err(Error::Browser(err.to_string())),
+    }
+}
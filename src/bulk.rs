@@ -0,0 +1,153 @@
+use crate::{Error, Result};
+
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+use std::io;
+
+/// The changes to make to a single issue. Fields that are left out (or
+/// empty) are not changed.
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Edit {
+    pub issue: String,
+    /// Name, email or account ID of the new assignee, or `unassigned`.
+    pub assignee: Option<String>,
+    #[serde(deserialize_with = "string_or_number")]
+    pub sprint: Option<String>,
+    /// Labels to add, or to remove when prefixed with `-`.
+    pub labels: Vec<String>,
+    /// New original estimate, e.g. `2d` or `4h 30m`.
+    pub estimate: Option<String>,
+    /// New remaining estimate.
+    pub remaining: Option<String>,
+}
+
+impl Edit {
+    /// Splits the labels into the ones to add and the ones to remove.
+    pub fn labels(&self) -> (Vec<&str>, Vec<&str>) {
+        let mut adds = Vec::new();
+        let mut removes = Vec::new();
+        for label in self.labels.iter() {
+            match label.strip_prefix('-') {
+                Some(label) => removes.push(label),
+                None => adds.push(label.trim_start_matches('+')),
+            }
+        }
+        (adds, removes)
+    }
+}
+
+fn string_or_number<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<String>, D::Error> {
+    Ok(match Option::<Value>::deserialize(deserializer)? {
+        Some(Value::String(v)) => Some(v),
+        Some(Value::Number(v)) => Some(v.to_string()),
+        _ => None,
+    })
+}
+
+/// Reads edits as JSON lines, or as CSV with a header row naming the fields
+/// (labels are separated by spaces or commas).
+pub fn edits<R: io::Read>(mut reader: R) -> Result<Vec<Edit>> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+
+    let edits = match input.trim_start().starts_with('{') {
+        true => json_lines(&input)?,
+        false => csv(&input)?,
+    };
+    match edits.iter().position(|v| v.issue.is_empty()) {
+        Some(index) => Err(Error::Edit(format!("edit {} has no issue", index + 1))),
+        None => Ok(edits),
+    }
+}
+
+fn json_lines(input: &str) -> Result<Vec<Edit>> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line)
+                .map_err(|err| Error::Edit(format!("line {}: {}", index + 1, err)))
+        })
+        .collect()
+}
+
+fn csv(input: &str) -> Result<Vec<Edit>> {
+    let mut reader = csv::Reader::from_reader(input.as_bytes());
+    let headers: Vec<String> = reader
+        .headers()?
+        .iter()
+        .map(|v| v.trim().to_lowercase())
+        .collect();
+
+    let mut edits = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        let mut edit = Edit::default();
+        for (header, value) in headers.iter().zip(record.iter()) {
+            let value = value.trim();
+            if value.is_empty() {
+                continue;
+            }
+            match header.as_str() {
+                "issue" => edit.issue = value.to_owned(),
+                "assignee" => edit.assignee = Some(value.to_owned()),
+                "sprint" => edit.sprint = Some(value.to_owned()),
+                "labels" => {
+                    edit.labels = value
+                        .split(|c: char| c == ',' || c.is_whitespace())
+                        .filter(|v| !v.is_empty())
+                        .map(|v| v.to_owned())
+                        .collect()
+                }
+                "estimate" => edit.estimate = Some(value.to_owned()),
+                "remaining" => edit.remaining = Some(value.to_owned()),
+                header => return Err(Error::Edit(format!("unknown column `{}`", header))),
+            }
+        }
+        edits.push(edit);
+    }
+    Ok(edits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edits_are_read_from_csv_and_json_lines() {
+        let expected = vec![
+            Edit {
+                issue: "ABC-1".to_owned(),
+                assignee: Some("jane".to_owned()),
+                sprint: Some("42".to_owned()),
+                labels: vec!["backend".to_owned(), "-tech-debt".to_owned()],
+                ..Default::default()
+            },
+            Edit {
+                issue: "ABC-2".to_owned(),
+                estimate: Some("2d".to_owned()),
+                remaining: Some("1d 4h".to_owned()),
+                ..Default::default()
+            },
+        ];
+
+        let csv = "Issue,Assignee,Sprint,Labels,Estimate,Remaining\n\
+                   ABC-1,jane,42,\"backend, -tech-debt\",,\n\
+                   ABC-2,,,,2d,1d 4h\n";
+        assert_eq!(edits(csv.as_bytes()).unwrap(), expected);
+
+        let json = "{\"issue\": \"ABC-1\", \"assignee\": \"jane\", \"sprint\": 42, \"labels\": [\"backend\", \"-tech-debt\"]}\n\
+                    \n\
+                    {\"issue\": \"ABC-2\", \"estimate\": \"2d\", \"remaining\": \"1d 4h\"}\n";
+        assert_eq!(edits(json.as_bytes()).unwrap(), expected);
+
+        assert_eq!(expected[0].labels(), (vec!["backend"], vec!["tech-debt"]));
+        assert!(edits("Key,Assignee\nABC-1,jane\n".as_bytes()).is_err());
+        assert!(edits("Assignee\njane\n".as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,42 @@
+use crate::Result;
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct Cache {
+    /// When `jira inbox` was last run, to only show what changed since.
+    pub inbox_visited: Option<String>,
+    pub boards: BTreeMap<String, String>,
+    pub sprints: BTreeMap<String, String>,
+}
+
+impl Cache {
+    pub fn load() -> Result<Self> {
+        let path = match Self::path() {
+            Some(path) if path.exists() => path,
+            _ => return Ok(Default::default()),
+        };
+
+        // A corrupt cache is not worth failing a command for, so just start over.
+        Ok(toml::from_str(&fs::read_to_string(path)?).unwrap_or_default())
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(path) = Self::path() {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(path, toml::to_string(self)?)?;
+        }
+        Ok(())
+    }
+
+    pub fn path() -> Option<PathBuf> {
+        dirs::cache_dir().map(|v| v.join("jira").join("cache.toml"))
+    }
+}
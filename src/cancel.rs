@@ -0,0 +1,27 @@
+use crate::{Error, Result};
+
+use log::warn;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static CANCELLED: AtomicBool = AtomicBool::new(false);
+
+/// Makes Ctrl-C stop a command between requests instead of killing it, so it
+/// never aborts halfway through writing an issue.
+pub fn install() {
+    if let Err(err) = ctrlc::set_handler(|| CANCELLED.store(true, Ordering::SeqCst)) {
+        warn!("unable to handle Ctrl-C: {}", err);
+    }
+}
+
+pub fn cancelled() -> bool {
+    CANCELLED.load(Ordering::SeqCst)
+}
+
+/// Returns `Error::Cancelled` with what was done so far when Ctrl-C was pressed.
+pub fn check<F: FnOnce() -> String>(done: F) -> Result<()> {
+    match cancelled() {
+        true => Err(Error::Cancelled(done())),
+        false => Ok(()),
+    }
+}
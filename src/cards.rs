@@ -0,0 +1,192 @@
+use crate::Result;
+
+use printpdf::{BuiltinFont, Color, Greyscale, Line, Mm, PdfDocument, Point};
+use qrcode::{render::svg, Color as QrColor, QrCode};
+
+use std::io::{BufWriter, Write};
+
+const PAGE_WIDTH: f64 = 210.0;
+const PAGE_HEIGHT: f64 = 297.0;
+const CARD_WIDTH: f64 = 95.0;
+const CARD_HEIGHT: f64 = 68.0;
+const MARGIN: f64 = 8.0;
+const QR_SIZE: f64 = 24.0;
+
+pub struct Card {
+    pub key: String,
+    pub summary: String,
+    pub assignee: String,
+    pub estimate: String,
+    pub url: String,
+}
+
+pub fn html(cards: &[Card]) -> Result<String> {
+    let mut output = String::from(HTML_HEADER);
+
+    for card in cards {
+        let qr = QrCode::new(card.url.as_bytes())?
+            .render::<svg::Color>()
+            .min_dimensions(96, 96)
+            .build();
+
+        output.push_str(&format!(
+            r#"<div class="card">
+  <div class="key">{}</div>
+  <div class="summary">{}</div>
+  <div class="footer">
+    <div><div>{}</div><div>{}</div></div>
+    <div class="qr">{}</div>
+  </div>
+</div>
+"#,
+            escape(&card.key),
+            escape(&card.summary),
+            escape(&card.assignee),
+            escape(&card.estimate),
+            qr,
+        ));
+    }
+
+    output.push_str(HTML_FOOTER);
+    Ok(output)
+}
+
+pub fn pdf<W: Write>(cards: &[Card], out: W) -> Result<()> {
+    let (doc, page, layer) =
+        PdfDocument::new("Issue cards", Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Cards");
+    let regular = doc.add_builtin_font(BuiltinFont::Helvetica)?;
+    let bold = doc.add_builtin_font(BuiltinFont::HelveticaBold)?;
+
+    let columns = ((PAGE_WIDTH - MARGIN) / (CARD_WIDTH + MARGIN)) as usize;
+    let rows = ((PAGE_HEIGHT - MARGIN) / (CARD_HEIGHT + MARGIN)) as usize;
+
+    let mut layer = doc.get_page(page).get_layer(layer);
+    for (i, card) in cards.iter().enumerate() {
+        if i > 0 && i % (columns * rows) == 0 {
+            let (page, index) = doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Cards");
+            layer = doc.get_page(page).get_layer(index);
+        }
+
+        let (column, row) = ((i % columns) as f64, ((i / columns) % rows) as f64);
+        let x = MARGIN + column * (CARD_WIDTH + MARGIN);
+        let y = PAGE_HEIGHT - MARGIN - CARD_HEIGHT - row * (CARD_HEIGHT + MARGIN);
+
+        layer.set_outline_color(Color::Greyscale(Greyscale::new(0.0, None)));
+        layer.set_outline_thickness(0.5);
+        layer.add_shape(rectangle(x, y, CARD_WIDTH, CARD_HEIGHT, false));
+
+        layer.use_text(
+            card.key.as_str(),
+            18.0,
+            Mm(x + 4.0),
+            Mm(y + CARD_HEIGHT - 10.0),
+            &bold,
+        );
+        for (n, line) in wrap(&card.summary, 42).iter().take(4).enumerate() {
+            layer.use_text(
+                line.as_str(),
+                10.0,
+                Mm(x + 4.0),
+                Mm(y + CARD_HEIGHT - 18.0 - n as f64 * 5.0),
+                &regular,
+            );
+        }
+        layer.use_text(
+            card.assignee.as_str(),
+            10.0,
+            Mm(x + 4.0),
+            Mm(y + 10.0),
+            &regular,
+        );
+        layer.use_text(
+            card.estimate.as_str(),
+            10.0,
+            Mm(x + 4.0),
+            Mm(y + 4.0),
+            &bold,
+        );
+
+        let qr = QrCode::new(card.url.as_bytes())?;
+        let (width, colors) = (qr.width(), qr.to_colors());
+        let module = QR_SIZE / width as f64;
+        let (qr_x, qr_y) = (x + CARD_WIDTH - QR_SIZE - 3.0, y + 3.0);
+
+        layer.set_fill_color(Color::Greyscale(Greyscale::new(0.0, None)));
+        for (n, color) in colors.iter().enumerate() {
+            if *color == QrColor::Dark {
+                layer.add_shape(rectangle(
+                    qr_x + (n % width) as f64 * module,
+                    qr_y + QR_SIZE - ((n / width) + 1) as f64 * module,
+                    module,
+                    module,
+                    true,
+                ));
+            }
+        }
+    }
+
+    Ok(doc.save(&mut BufWriter::new(out))?)
+}
+
+fn rectangle(x: f64, y: f64, width: f64, height: f64, fill: bool) -> Line {
+    Line {
+        points: vec![
+            (Point::new(Mm(x), Mm(y)), false),
+            (Point::new(Mm(x + width), Mm(y)), false),
+            (Point::new(Mm(x + width), Mm(y + height)), false),
+            (Point::new(Mm(x), Mm(y + height)), false),
+        ],
+        is_closed: true,
+        has_fill: fill,
+        has_stroke: !fill,
+        is_clipping_path: false,
+    }
+}
+
+fn wrap(input: &str, width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+
+    for word in input.split_whitespace() {
+        if !line.is_empty() && line.len() + word.len() + 1 > width {
+            lines.push(line);
+            line = String::new();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+
+    lines
+}
+
+pub(crate) fn escape(input: &str) -> String {
+    input
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const HTML_HEADER: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Issue cards</title>
+<style>
+  body { font-family: Helvetica, Arial, sans-serif; margin: 0; }
+  .card { display: inline-block; box-sizing: border-box; width: 95mm; height: 68mm; margin: 4mm; padding: 4mm; border: 1px solid #000; vertical-align: top; position: relative; page-break-inside: avoid; }
+  .key { font-size: 18pt; font-weight: bold; }
+  .summary { font-size: 11pt; margin-top: 2mm; max-height: 28mm; overflow: hidden; }
+  .footer { position: absolute; left: 4mm; right: 4mm; bottom: 3mm; display: flex; justify-content: space-between; align-items: flex-end; font-size: 10pt; }
+  .qr svg { width: 24mm; height: 24mm; }
+</style>
+</head>
+<body>
+"#;
+
+const HTML_FOOTER: &str = "</body>\n</html>\n";
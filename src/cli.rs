@@ -0,0 +1,20 @@
+//! The arguments of the `jira` binary, and how its commands map to the
+//! client and are printed.
+
+pub mod app;
+pub mod commands;
+pub mod completions;
+pub mod repl;
+#[cfg(feature = "serve")]
+pub mod serve;
+pub mod terminal;
+
+use clap::ArgMatches;
+
+/// Returns the matches of the innermost subcommand.
+pub fn leaf<'a>(matches: &'a ArgMatches<'a>) -> &'a ArgMatches<'a> {
+    match matches.subcommand() {
+        (_, Some(options)) => leaf(options),
+        _ => matches,
+    }
+}
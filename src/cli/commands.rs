@@ -0,0 +1,660 @@
+use super::terminal::Terminal;
+
+use jira::bulk;
+use jira::options::{
+    Clipboard, CloneOptions, EstimateOptions, GrepOptions, IssuesOptions, MatrixOptions,
+    NewSubtask, PublishOptions, ReportOptions, Sources, SprintsOptions, UpdateOptions,
+};
+use jira::stats::Stats;
+use jira::tracker::{self, Mapping};
+use jira::{Client, Config, Error, Output, Result};
+
+use chrono::{Duration, NaiveDate, Utc};
+use clap::ArgMatches;
+#[cfg(feature = "serve")]
+use prettytable::{Row, Table};
+
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+use std::rc::Rc;
+
+/// Creates a client from the global arguments, printing what its commands
+/// output on the terminal.
+pub fn client(options: &ArgMatches, terminal: Rc<Terminal>) -> Result<Client> {
+    let host = match options.value_of("host") {
+        Some(host) => host.trim_end_matches('/').to_owned(),
+        None => format!(
+            "https://{}.atlassian.net",
+            options
+                .value_of("organization")
+                .ok_or(Error::Config("organization".to_owned()))?
+        ),
+    };
+
+    let (user, token) = (
+        options
+            .value_of("user")
+            .ok_or(Error::Config("user".to_owned()))?,
+        options
+            .value_of("token")
+            .ok_or(Error::Config("token".to_owned()))?,
+    );
+
+    let mut builder = Client::builder()
+        .host(&host)
+        .credentials(user, token)
+        .config(Config::load()?)
+        .output(terminal)
+        .a11y(options.is_present("a11y"))
+        .timings(options.is_present("timings"));
+    if let Some(seconds) = options.value_of("timeout").and_then(|v| v.parse().ok()) {
+        builder = builder.timeout(std::time::Duration::from_secs(seconds));
+    }
+    if let Some(seconds) = options
+        .value_of("connect-timeout")
+        .and_then(|v| v.parse().ok())
+    {
+        builder = builder.connect_timeout(std::time::Duration::from_secs(seconds));
+    }
+    if let Some(style) = options
+        .value_of("duration-format")
+        .and_then(|v| v.parse().ok())
+    {
+        builder = builder.duration_style(style);
+    }
+    if let Some(url) = options.value_of("proxy") {
+        builder = builder.proxy(url);
+    }
+    if let Some(path) = options.value_of("ca-bundle") {
+        builder = builder.ca_bundle(Path::new(path));
+    }
+    builder = builder.insecure(options.is_present("insecure"));
+    if let Some(tz) = options.value_of("instance-tz").and_then(|v| v.parse().ok()) {
+        builder = builder.instance_tz(tz);
+    }
+    #[cfg(feature = "tempo")]
+    if let Some(token) = options.value_of("tempo-token") {
+        builder = builder.tempo_token(token);
+    }
+    builder.build()
+}
+
+pub fn dispatch(client: &Client, terminal: &Terminal, matches: &ArgMatches) -> Result<()> {
+    terminal.select(super::leaf(matches));
+
+    match matches.subcommand() {
+        ("boards", Some(options)) => match options.subcommand() {
+            ("show", Some(options)) => client.board(required(options, "board")?),
+            ("create", Some(options)) => {
+                let (project_key, filter_id) =
+                    (options.value_of("project"), options.value_of("filter-id"));
+                client.board_create(required(options, "name")?, project_key, filter_id)
+            }
+            ("view", Some(options)) => client.view(required(options, "board")?),
+            _ => client.boards(options.is_present("quiet")),
+        },
+        ("sprints", Some(options)) => match options.subcommand() {
+            ("notes", Some(options)) => {
+                let notes = match options.value_of("file") {
+                    Some(path) => Some(fs::read_to_string(path)?),
+                    None => None,
+                };
+                client.notes(required(options, "sprint")?, notes.as_deref())
+            }
+            ("carry-over", Some(options)) => client.carry_over(
+                required(options, "from")?,
+                required(options, "to")?,
+                options.is_present("reset-remaining"),
+                options.is_present("diff"),
+            ),
+            ("health", Some(options)) => client.health(required(options, "sprint")?),
+            ("diff", Some(options)) => {
+                client.sprint_diff(required(options, "sprint")?, day(options, "since")?)
+            }
+            _ => client.sprints(&SprintsOptions {
+                board: required(options, "board")?.to_owned(),
+                all: options.is_present("all"),
+                active: options.is_present("active"),
+                future: options.is_present("future"),
+                quiet: options.is_present("quiet"),
+                ics: options.value_of("output") == Some("ics"),
+                ceremonies: options.is_present("ceremonies"),
+            }),
+        },
+        ("issues", Some(options)) => match options.subcommand() {
+            ("cards", Some(options)) => {
+                client.cards(&sources(options), options.value_of("format") == Some("pdf"))
+            }
+            ("qr", Some(options)) => client.qr(required(options, "issue")?),
+            ("open", Some(options)) => client.open(required(options, "issue")?),
+            ("grep", Some(options)) => client.grep(&grep_options(options)?),
+            ("estimate", Some(options)) => client.estimate(&estimate_options(options)?),
+            ("bulk-edit", Some(options)) => {
+                let edits = match options.value_of("from") {
+                    Some("-") | None => bulk::edits(io::stdin())?,
+                    Some(path) => bulk::edits(fs::File::open(path)?)?,
+                };
+                client.bulk_edit(&edits, options.is_present("diff"))
+            }
+            ("label", Some(options)) => client.labels(&update_options(options)),
+            ("component", Some(options)) => client.components(&update_options(options)),
+            ("link", Some(options)) => {
+                let (link_type, target) = link(options)?;
+                client.link(required(options, "issue")?, link_type, target)
+            }
+            ("links", Some(options)) => client.links(required(options, "issue")?),
+            ("clone", Some(options)) => client.clone_issue(&CloneOptions {
+                issue: required(options, "issue")?.to_owned(),
+                summary: options.value_of("summary").map(|v| v.to_owned()),
+                subtasks: options.is_present("subtasks"),
+                sprint: options.value_of("sprint").map(|v| v.to_owned()),
+            }),
+            ("remote-link", Some(options)) => client.remote_link(
+                required(options, "issue")?,
+                required(options, "url")?,
+                options.value_of("title"),
+            ),
+            ("subtask", Some(options)) => {
+                client.subtask(required(options, "issue")?, subtasks(options)?)
+            }
+            ("activity", Some(options)) => client.activity(required(options, "issue")?),
+            ("history", Some(options)) => {
+                let fields: Vec<&str> = options.values_of("field").unwrap_or_default().collect();
+                client.history(required(options, "issue")?, &fields)
+            }
+            ("watch", Some(options)) => {
+                let keys: Vec<&str> = options.values_of("issue").unwrap_or_default().collect();
+                let users: Vec<&str> = match options.values_of("watcher") {
+                    Some(users) => users.collect(),
+                    None => vec!["me"],
+                };
+                client.watch(&keys, &users, options.is_present("remove"))
+            }
+            ("flag", Some(options)) => {
+                let keys: Vec<&str> = options.values_of("issue").unwrap_or_default().collect();
+                client.flag(&keys, options.is_present("clear"))
+            }
+            ("watchers", Some(options)) => client.watchers(required(options, "issue")?),
+            ("attach", Some(options)) => {
+                let files: Vec<&Path> = options
+                    .values_of("file")
+                    .unwrap_or_default()
+                    .map(Path::new)
+                    .collect();
+                client.attach(required(options, "issue")?, &files)
+            }
+            ("attachments", Some(options)) => client.attachments(
+                required(options, "issue")?,
+                options.value_of("download").map(Path::new),
+            ),
+            ("show", Some(options)) => client.show(required(options, "issue")?, clipboard(options)),
+            _ => client.issues(&issues_options(options)),
+        },
+        ("report", Some(options)) => match options.subcommand() {
+            ("conflicts", Some(options)) => client.conflicts(&values(options, "board")),
+            ("matrix", Some(options)) => client.matrix(&MatrixOptions {
+                sources: sources(options),
+                count: options.is_present("count"),
+                by_type: options.value_of("by") == Some("type"),
+            }),
+            _ => client.report(&report_options(options)?),
+        },
+        ("publish", Some(options)) => client.publish(&PublishOptions {
+            report: report_options(options)?,
+            space: required(options, "space")?.to_owned(),
+            title: options.value_of("title").map(|v| v.to_owned()),
+            confluence_url: options.value_of("confluence-url").map(|v| v.to_owned()),
+        }),
+        ("reconcile", Some(options)) => client.reconcile(&sources(options)),
+        ("users", Some(options)) => match options.subcommand() {
+            ("search", Some(options)) => client.users(required(options, "query")?),
+            _ => unreachable!(),
+        },
+        ("projects", Some(_)) => client.projects(),
+        ("filters", Some(_)) => client.filters(),
+        ("inbox", Some(options)) => client.inbox(options.value_of("since")),
+        ("mine", Some(_)) => client.mine(),
+        ("standup", Some(options)) => {
+            client.standup(options.value_of("for"), options.value_of("since"))
+        }
+        ("whoami", Some(_)) => client.whoami(),
+        ("export-metrics", Some(options)) => {
+            #[cfg(feature = "serve")]
+            {
+                if let Some(listen) = options.value_of("listen") {
+                    return super::serve::metrics(client, listen, options);
+                }
+            }
+            client.export_metrics(&sources(options))
+        }
+        ("versions", Some(options)) => match options.subcommand() {
+            ("create", Some(options)) => client.version_create(
+                required(options, "project")?,
+                required(options, "name")?,
+                options.value_of("release-date"),
+            ),
+            ("release", Some(options)) => client.version_release(
+                required(options, "project")?,
+                required(options, "version")?,
+                options.value_of("date"),
+            ),
+            _ => client.versions(required(options, "project")?),
+        },
+        ("epics", Some(options)) => match options.subcommand() {
+            ("progress", Some(options)) => client.epics(required(options, "project")?),
+            _ => unreachable!(),
+        },
+        ("worklog", Some(options)) => match options.subcommand() {
+            ("audit", Some(options)) => client.worklog_audit(&sources(options)),
+            _ => unreachable!(),
+        },
+        ("import", Some(options)) => match options.subcommand() {
+            ("tracker", Some(options)) => {
+                let mapping: Mapping =
+                    serde_yaml::from_reader(fs::File::open(required(options, "mapping")?)?)?;
+                let tasks = match options.value_of("file") {
+                    Some("-") | None => tracker::tasks(io::stdin(), &mapping, client.durations())?,
+                    Some(path) => {
+                        tracker::tasks(fs::File::open(path)?, &mapping, client.durations())?
+                    }
+                };
+                client.import_tasks(&mapping, &tasks, required(options, "sprint")?)
+            }
+            _ => unreachable!(),
+        },
+        ("timer", Some(options)) => match options.subcommand() {
+            ("start", Some(options)) => client.timer_start(required(options, "issue")?),
+            ("stop", Some(options)) => client.timer_stop(options.value_of("round")),
+            ("status", Some(_)) => client.timer_status(),
+            _ => unreachable!(),
+        },
+        ("automation", Some(options)) => match options.subcommand() {
+            ("list", Some(options)) => client.automation(required(options, "project")?),
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    }
+}
+
+/// Returns the titles and rows of an `issues` or `report` command.
+#[cfg(feature = "serve")]
+pub fn table(client: &Client, matches: &ArgMatches) -> Result<(Row, Table)> {
+    match matches.subcommand() {
+        ("issues", Some(options)) => client.issues_table(&issues_options(options)),
+        ("report", Some(options)) => client.report_table(&report_options(options)?),
+        (command, _) => Err(Error::Config(command.to_owned())),
+    }
+}
+
+/// Returns the table of an `issues` or `report` command as a list of JSON objects.
+#[cfg(feature = "serve")]
+pub fn json(client: &Client, matches: &ArgMatches) -> Result<serde_json::Value> {
+    let (titles, table) = table(client, matches)?;
+    Ok(jira::output::json_rows(&titles, &table))
+}
+
+/// Prints the recorded velocity, or the load per assignee with `load`.
+#[cfg(feature = "history")]
+pub fn history(terminal: &Terminal, options: &ArgMatches) -> Result<()> {
+    let history = jira::history::History::open()?;
+    let durations = Config::load()?.durations();
+
+    let (titles, table) = match options.subcommand() {
+        ("load", Some(options)) => history.load_table(
+            options.value_of("assignee"),
+            options
+                .value_of("days")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+            &durations,
+        )?,
+        _ => history.velocity_table(&durations)?,
+    };
+
+    terminal.table(
+        "",
+        titles,
+        table,
+        "Nothing was recorded (hint: set `history = true` in the config file)",
+    );
+    Ok(())
+}
+
+/// Prints how often the commands were run, or clears the usage stats.
+pub fn stats(terminal: &Terminal, options: &ArgMatches) -> Result<()> {
+    if options.is_present("clear") {
+        Stats::clear()?;
+        terminal.message("Cleared the usage stats");
+        return Ok(());
+    }
+
+    let since = options
+        .value_of("days")
+        .and_then(|v| v.parse().ok())
+        .map(|days| Utc::now() - Duration::days(days));
+    let (titles, table) = Stats::load()?.table(since);
+
+    terminal.table(
+        "",
+        titles,
+        table,
+        "No usage was recorded (hint: set `stats = true` in the config file)",
+    );
+    Ok(())
+}
+
+pub fn issues_options(options: &ArgMatches) -> IssuesOptions {
+    IssuesOptions {
+        sources: sources(options),
+        assignee: options.value_of("assignee").map(|v| v.to_owned()),
+        issue: options.value_of("issue").map(|v| v.to_owned()),
+        all: options.is_present("all"),
+        no_subtasks: options.is_present("no-subtasks"),
+        flagged: options.is_present("flagged"),
+        components: values(options, "component"),
+        fix_version: options.value_of("fix-version").map(|v| v.to_owned()),
+        search: options.value_of("search").map(|v| v.to_owned()),
+        summary_only: options.is_present("summary-only"),
+        priorities: values(options, "priority"),
+        status_categories: values(options, "status-category"),
+        overdue: options.is_present("overdue"),
+        due_within: options.value_of("due-within").map(|v| v.to_owned()),
+        due: options.is_present("due"),
+        sort_by_priority: options.value_of("sort") == Some("priority"),
+        urls: options.is_present("urls") || options.value_of("output") == Some("json"),
+        fields: values(options, "field"),
+        watchers: options.is_present("watchers"),
+        copy: clipboard(options),
+        checks: values(options, "check"),
+    }
+}
+
+pub fn report_options(options: &ArgMatches) -> Result<ReportOptions> {
+    let logged = match options.is_present("from") {
+        true => Some((day(options, "from")?, day(options, "to")?)),
+        false => None,
+    };
+
+    Ok(ReportOptions {
+        sources: sources(options),
+        planning: options.is_present("planning"),
+        reset: options.is_present("reset"),
+        zero_done_remaining: options.is_present("zero-done-remaining"),
+        clear_done_remaining: options.is_present("clear-done-remaining"),
+        rollup_worklogs: options.is_present("rollup-worklogs"),
+        diff: options.is_present("diff"),
+        only: values(options, "only"),
+        exclude: values(options, "exclude"),
+        components: values(options, "component"),
+        by_component: options.value_of("group-by") == Some("component"),
+        split_co_assignees: options.is_present("split-co-assignees"),
+        detail: options.is_present("detail"),
+        logged,
+        compare: options.value_of("compare").map(|v| v.to_owned()),
+        save: options.value_of("save").map(|v| v.to_owned()),
+        checks: values(options, "check"),
+        teams_webhook: options.value_of("teams-webhook").map(|v| v.to_owned()),
+        html: options.value_of("output") == Some("html"),
+        title: options.value_of("title").map(|v| v.to_owned()),
+        file: options.value_of("file").map(|v| v.to_owned()),
+    })
+}
+
+pub fn estimate_options(options: &ArgMatches) -> Result<EstimateOptions> {
+    Ok(EstimateOptions {
+        issue: required(options, "issue")?.to_owned(),
+        original: options.value_of("original").map(|v| v.to_owned()),
+        remaining: options.value_of("remaining").map(|v| v.to_owned()),
+        points: options.value_of("points").map(|v| v.parse().unwrap_or(0.0)),
+        board: options.value_of("board").map(|v| v.to_owned()),
+    })
+}
+
+fn grep_options(options: &ArgMatches) -> Result<GrepOptions> {
+    Ok(GrepOptions {
+        sources: sources(options),
+        pattern: required(options, "pattern")?.to_owned(),
+        ignore_case: options.is_present("ignore-case"),
+        all: options.is_present("all"),
+        color: io::stdout().is_terminal(),
+    })
+}
+
+fn update_options(options: &ArgMatches) -> UpdateOptions {
+    UpdateOptions {
+        issues: values(options, "issue"),
+        add: values(options, "add"),
+        remove: values(options, "remove"),
+        diff: options.is_present("diff"),
+    }
+}
+
+/// The boards, sprints, saved filter or JQL query given to a command.
+pub fn sources(options: &ArgMatches) -> Sources {
+    Sources {
+        boards: values(options, "board"),
+        sprints: values(options, "sprint"),
+        filter_id: options.value_of("filter-id").map(|v| v.to_owned()),
+        filter: options.value_of("filter").map(|v| v.to_owned()),
+        jql: options.value_of("jql").map(|v| v.to_owned()),
+        all_active: options.is_present("all-active"),
+    }
+}
+
+/// The subtasks of a YAML file (or stdin with `-`), or the one given with
+/// the flags.
+fn subtasks(options: &ArgMatches) -> Result<Vec<NewSubtask>> {
+    Ok(match options.value_of("from") {
+        Some("-") => serde_yaml::from_reader(io::stdin())?,
+        Some(path) => serde_yaml::from_reader(fs::File::open(path)?)?,
+        None => vec![NewSubtask {
+            summary: required(options, "summary")?.to_owned(),
+            estimate: options.value_of("estimate").map(|v| v.to_owned()),
+            assignee: options.value_of("assignee").map(|v| v.to_owned()),
+        }],
+    })
+}
+
+/// Returns the link type and the issue to link to.
+fn link<'a>(options: &'a ArgMatches) -> Result<(&'a str, &'a str)> {
+    Ok(
+        match (
+            options.value_of("blocks"),
+            options.value_of("relates-to"),
+            options.value_of("duplicates"),
+            options.value_of("type"),
+        ) {
+            (Some(target), _, _, _) => ("Blocks", target),
+            (_, Some(target), _, _) => ("Relates", target),
+            (_, _, Some(target), _) => ("Duplicate", target),
+            (_, _, _, Some(link_type)) => (link_type, required(options, "to")?),
+            _ => return Err(Error::Config("blocks".to_owned())),
+        },
+    )
+}
+
+fn clipboard(options: &ArgMatches) -> Option<Clipboard> {
+    match (options.is_present("copy"), options.is_present("copy-key")) {
+        (true, _) => Some(Clipboard::Url),
+        (_, true) => Some(Clipboard::Key),
+        _ => None,
+    }
+}
+
+fn required<'a>(options: &'a ArgMatches, name: &str) -> Result<&'a str> {
+    options.value_of(name).ok_or(Error::Config(name.to_owned()))
+}
+
+fn values(options: &ArgMatches, name: &str) -> Vec<String> {
+    options
+        .values_of(name)
+        .map(|v| v.map(|v| v.to_owned()).collect())
+        .unwrap_or_default()
+}
+
+/// Parses a `YYYY-MM-DD` date argument.
+fn day(options: &ArgMatches, name: &str) -> Result<NaiveDate> {
+    let day = required(options, name)?;
+    NaiveDate::parse_from_str(day, "%Y-%m-%d").map_err(|_| Error::Config(day.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::app::app;
+
+    use clap::ErrorKind;
+
+    use std::iter;
+
+    const CREDENTIALS: &[&str] = &[
+        "--host",
+        "https://example.atlassian.net",
+        "-u",
+        "jane@example.com",
+        "-t",
+        "secret",
+    ];
+
+    /// Parses a command with the arguments of the binary, returning the
+    /// matches of the innermost subcommand the options are read from.
+    fn matches(args: &[&str]) -> std::result::Result<ArgMatches<'static>, ErrorKind> {
+        let args = iter::once("jira")
+            .chain(args.iter().copied())
+            .chain(CREDENTIALS.iter().copied());
+        let mut matches = app().get_matches_from_safe(args).map_err(|err| err.kind)?;
+        while let Some(subcommand) = matches.subcommand.take() {
+            matches = subcommand.matches;
+        }
+        Ok(matches)
+    }
+
+    #[test]
+    fn issues_arguments_are_mapped_to_options() {
+        let options = issues_options(
+            &matches(&[
+                "issues",
+                "-b",
+                "1",
+                "-b",
+                "2",
+                "--issue",
+                "ABC-1",
+                "--copy-key",
+                "--status-category",
+                "todo",
+                "--sort",
+                "priority",
+                "--output",
+                "json",
+            ])
+            .unwrap(),
+        );
+
+        assert_eq!(options.sources.boards, ["1", "2"]);
+        assert_eq!(options.issue.as_deref(), Some("ABC-1"));
+        assert_eq!(options.copy, Some(Clipboard::Key));
+        assert_eq!(options.status_categories, ["todo"]);
+        assert!(options.sort_by_priority);
+        assert!(options.urls);
+
+        assert_eq!(
+            matches(&["issues", "-b", "1", "--status-category", "later"]).err(),
+            Some(ErrorKind::InvalidValue)
+        );
+        assert_eq!(
+            matches(&["issues", "-b", "1", "--copy"]).err(),
+            Some(ErrorKind::MissingRequiredArgument)
+        );
+    }
+
+    #[test]
+    fn boards_are_created_from_a_filter_id() {
+        let options = matches(&["boards", "create", "Team A", "--filter-id", "10000"]).unwrap();
+
+        assert_eq!(options.value_of("filter-id"), Some("10000"));
+        assert_eq!(
+            matches(&["boards", "create", "Team A", "--filter-id", "Team A"]).err(),
+            Some(ErrorKind::ValueValidation)
+        );
+    }
+
+    #[test]
+    fn estimate_takes_the_issue_as_an_argument() {
+        let options = estimate_options(
+            &matches(&[
+                "issues",
+                "estimate",
+                "ABC-1",
+                "--points",
+                "3",
+                "--board-id",
+                "1",
+            ])
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(options.issue, "ABC-1");
+        assert_eq!(options.points, Some(3.0));
+        assert_eq!(options.board.as_deref(), Some("1"));
+
+        assert_eq!(
+            matches(&["issues", "estimate", "ABC-1", "--points", "3"]).err(),
+            Some(ErrorKind::MissingRequiredArgument)
+        );
+        assert_eq!(
+            matches(&["issues", "estimate", "ABC-1"]).err(),
+            Some(ErrorKind::MissingRequiredArgument)
+        );
+    }
+
+    #[test]
+    fn report_arguments_are_mapped_to_options() {
+        let options = report_options(
+            &matches(&[
+                "report",
+                "-s",
+                "10",
+                "--group-by",
+                "component",
+                "--check",
+                "capacity",
+            ])
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(options.sources.sprints, ["10"]);
+        assert!(options.by_component);
+        assert_eq!(options.checks, ["capacity"]);
+        assert_eq!(options.logged, None);
+
+        let options = report_options(
+            &matches(&[
+                "report",
+                "-s",
+                "10",
+                "--from",
+                "2020-05-01",
+                "--to",
+                "2020-05-31",
+            ])
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            options.logged,
+            Some((
+                NaiveDate::from_ymd_opt(2020, 5, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2020, 5, 31).unwrap()
+            ))
+        );
+        assert_eq!(
+            matches(&["report", "-s", "10", "--group-by", "component", "--detail"]).err(),
+            Some(ErrorKind::ArgumentConflict)
+        );
+    }
+}
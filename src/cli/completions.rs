@@ -0,0 +1,86 @@
+use jira::{Cache, Error, Result};
+
+use clap::{App, Shell};
+
+use std::io;
+
+const BIN_NAME: &str = "jira";
+
+pub fn print(mut app: App, options: &clap::ArgMatches) -> Result<()> {
+    if let Some(list) = options.value_of("list") {
+        let cache = Cache::load()?;
+        let entries = match list {
+            "boards" => cache.boards,
+            _ => cache.sprints,
+        };
+        for (id, name) in entries {
+            println!("{}\t{}", id, name);
+        }
+        return Ok(());
+    }
+
+    let shell = options
+        .value_of("shell")
+        .ok_or(Error::Config("shell".to_owned()))?;
+
+    app.gen_completions_to(
+        BIN_NAME,
+        shell
+            .parse::<Shell>()
+            .map_err(|_| Error::Config("shell".to_owned()))?,
+        &mut io::stdout(),
+    );
+
+    // The generated scripts only know about flags, so hook in the board and
+    // sprint IDs from the cache for the shells that allow us to do so.
+    match shell {
+        "bash" => println!("{}", BASH_DYNAMIC),
+        "zsh" => println!("{}", ZSH_DYNAMIC),
+        "fish" => println!("{}", FISH_DYNAMIC),
+        _ => (),
+    }
+
+    Ok(())
+}
+
+const BASH_DYNAMIC: &str = r#"
+_jira_dynamic() {
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    case "${COMP_WORDS[COMP_CWORD-1]}" in
+        -b|--board-id)
+            COMPREPLY=($(compgen -W "$(jira completions --list boards 2>/dev/null | cut -f1)" -- "${cur}"))
+            return 0
+            ;;
+        -s|--sprint-id)
+            COMPREPLY=($(compgen -W "$(jira completions --list sprints 2>/dev/null | cut -f1)" -- "${cur}"))
+            return 0
+            ;;
+    esac
+    _jira "$@"
+}
+
+complete -F _jira_dynamic -o bashdefault -o default jira"#;
+
+const ZSH_DYNAMIC: &str = r#"
+_jira_dynamic() {
+    local -a ids
+    case "${words[CURRENT-1]}" in
+        -b|--board-id)
+            ids=(${(f)"$(jira completions --list boards 2>/dev/null | tr '\t' ':')"})
+            _describe 'board' ids
+            return
+            ;;
+        -s|--sprint-id)
+            ids=(${(f)"$(jira completions --list sprints 2>/dev/null | tr '\t' ':')"})
+            _describe 'sprint' ids
+            return
+            ;;
+    esac
+    _jira "$@"
+}
+
+compdef _jira_dynamic jira"#;
+
+const FISH_DYNAMIC: &str = r#"
+complete -c jira -s b -l board-id -x -a "(jira completions --list boards 2>/dev/null)"
+complete -c jira -s s -l sprint-id -x -a "(jira completions --list sprints 2>/dev/null)""#;
@@ -0,0 +1,321 @@
+use super::leaf;
+
+use jira::shell;
+use jira::{Config, Result};
+
+use clap::{App, ArgMatches, ErrorKind};
+
+use std::io::{self, BufRead, Write};
+use std::iter;
+
+const SOURCE_FLAGS: &[&str] = &[
+    "-b",
+    "--board-id",
+    "-s",
+    "--sprint-id",
+    "-q",
+    "--jql",
+    "--filter-id",
+    "--filter",
+];
+
+/// The global arguments that can be passed on to the commands of a session,
+/// with their short flags.
+const CREDENTIALS: &[(&str, Option<&str>)] = &[
+    ("organization", Some("-o")),
+    ("host", None),
+    ("user", Some("-u")),
+    ("token", Some("-t")),
+    ("tempo-token", None),
+];
+
+/// The global arguments a session started with. Commands parsed later on get
+/// them as arguments, as exporting them would hand them to every process the
+/// session starts too (hooks, the browser and the clipboard).
+pub(crate) struct Credentials(Vec<(&'static str, String)>);
+
+#[derive(Default)]
+struct Context {
+    board: Option<String>,
+    sprint: Option<String>,
+}
+
+pub fn run<A, F>(app: A, options: &ArgMatches, mut dispatch: F) -> Result<()>
+where
+    A: Fn() -> App<'static, 'static>,
+    F: FnMut(&ArgMatches) -> Result<()>,
+{
+    let credentials = Credentials::new(options);
+    let parse = |words: &[String]| self::parse(&app, &credentials, words);
+
+    let config = Config::load()?;
+    let mut context = Context::default();
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    loop {
+        print!("{}", context.prompt());
+        io::stdout().flush()?;
+
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => {
+                println!();
+                return Ok(());
+            }
+        };
+
+        let mut words = match shell::split(&line) {
+            Some(words) => words,
+            None => {
+                eprintln!("Error: unbalanced quotes");
+                continue;
+            }
+        };
+
+        match words.first().map(|v| v.as_str()) {
+            None => continue,
+            Some("exit") | Some("quit") => return Ok(()),
+            Some("use") => {
+                context.update(&words[1..]);
+                continue;
+            }
+            Some("repl") | Some("completions") | Some("serve") | Some("stats") => {
+                eprintln!("Error: `{}` is not available in the REPL", words[0]);
+                continue;
+            }
+            _ => context.apply(&mut words, parse),
+        }
+
+        let matches = match parse(&words) {
+            Ok(matches) => matches,
+            Err(err) if err.kind == ErrorKind::HelpDisplayed => {
+                println!("{}", err.message);
+                continue;
+            }
+            // Aliases never shadow commands, so they are only tried when
+            // the line doesn't parse as a command.
+            Err(err) => match config.expand_alias(&args(&words)) {
+                Some(args) => {
+                    let mut words = args[1..].to_vec();
+                    context.apply(&mut words, parse);
+                    match parse(&words) {
+                        Ok(matches) => matches,
+                        Err(err) => {
+                            eprintln!("{}", err.message);
+                            continue;
+                        }
+                    }
+                }
+                None => {
+                    eprintln!("{}", err.message);
+                    continue;
+                }
+            },
+        };
+
+        if let Err(err) = dispatch(&matches) {
+            eprintln!("Error: {}", err);
+        }
+    }
+}
+
+/// Parses the words of a line as a command. The credentials the session
+/// started with are only added when the command misses them, so the ones
+/// given in the environment or on the line itself are used first.
+fn parse<A>(
+    app: &A,
+    credentials: &Credentials,
+    words: &[String],
+) -> clap::Result<ArgMatches<'static>>
+where
+    A: Fn() -> App<'static, 'static>,
+{
+    let args = args(words);
+    match app().get_matches_from_safe(&args) {
+        Err(err) if err.kind == ErrorKind::MissingRequiredArgument => {
+            match app().get_matches_from_safe(credentials.add_to(args)) {
+                // Commands without global arguments miss something else.
+                Err(retry) if retry.kind == ErrorKind::UnknownArgument => Err(err),
+                result => result,
+            }
+        }
+        result => result,
+    }
+}
+
+fn args(words: &[String]) -> Vec<String> {
+    iter::once("jira".to_owned())
+        .chain(words.iter().cloned())
+        .collect()
+}
+
+impl Credentials {
+    pub(crate) fn new(options: &ArgMatches) -> Self {
+        Credentials(
+            CREDENTIALS
+                .iter()
+                .filter_map(|(name, _)| Some((*name, options.value_of(name)?.to_owned())))
+                .collect(),
+        )
+    }
+
+    /// Adds the credentials that aren't given in the arguments yet.
+    pub(crate) fn add_to(&self, mut args: Vec<String>) -> Vec<String> {
+        for (name, value) in self.0.iter() {
+            let long = format!("--{}", name);
+            let short = CREDENTIALS
+                .iter()
+                .find(|(v, _)| v == name)
+                .and_then(|(_, short)| *short);
+            let given = args.iter().any(|v| {
+                *v == long
+                    || v.starts_with(&format!("{}=", long))
+                    || short.is_some_and(|short| v.starts_with(short))
+            });
+            if !given {
+                args.push(long);
+                args.push(value.clone());
+            }
+        }
+        args
+    }
+}
+
+impl Context {
+    fn prompt(&self) -> String {
+        match (&self.board, &self.sprint) {
+            (_, Some(sprint)) => format!("jira (sprint {})> ", sprint),
+            (Some(board), None) => format!("jira (board {})> ", board),
+            (None, None) => "jira> ".to_owned(),
+        }
+    }
+
+    fn update(&mut self, args: &[String]) {
+        match args {
+            [kind, id] if kind == "board" || kind == "sprint" => {
+                if id.parse::<u64>().is_err() {
+                    eprintln!("Error: {} ID is not a number", kind);
+                    return;
+                }
+                match kind.as_str() {
+                    "board" => self.board = Some(id.clone()),
+                    _ => self.sprint = Some(id.clone()),
+                }
+            }
+            [none] if none == "none" => *self = Context::default(),
+            [] => println!(
+                "board: {}, sprint: {}",
+                self.board.as_deref().unwrap_or("-"),
+                self.sprint.as_deref().unwrap_or("-")
+            ),
+            _ => eprintln!("Usage: use [board <ID> | sprint <ID> | none]"),
+        }
+    }
+
+    /// Adds the sprint or board in use to a command that takes a sprint or
+    /// board ID, preferring the sprint, unless the command already selects
+    /// what to work on.
+    fn apply<P>(&self, words: &mut Vec<String>, parse: P)
+    where
+        P: Fn(&[String]) -> clap::Result<ArgMatches<'static>>,
+    {
+        if words.iter().any(|v| SOURCE_FLAGS.contains(&v.as_str())) {
+            return;
+        }
+
+        let mut candidates = Vec::new();
+        if let Some(sprint) = &self.sprint {
+            candidates.push(("sprint", vec!["--sprint-id".to_owned(), sprint.clone()]));
+        }
+        if let Some(board) = &self.board {
+            candidates.push(("board", vec!["--board-id".to_owned(), board.clone()]));
+            candidates.push(("board", vec![board.clone()]));
+        }
+
+        for (name, extra) in candidates {
+            let mut candidate = words.clone();
+            candidate.extend(extra.iter().cloned());
+            // Clap rejects unknown arguments before it checks for missing
+            // ones, so a command missing something else still takes an ID
+            // flag. Positional IDs must end up in an argument of that name.
+            let takes = match parse(&candidate) {
+                Ok(matches) => leaf(&matches).value_of(name) == extra.last().map(|v| v.as_str()),
+                Err(err) => extra.len() == 2 && err.kind == ErrorKind::MissingRequiredArgument,
+            };
+            if takes {
+                *words = candidate;
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::app::app;
+
+    fn words(line: &str) -> Vec<String> {
+        line.split_whitespace().map(|v| v.to_owned()).collect()
+    }
+
+    fn credentials() -> Credentials {
+        Credentials(vec![
+            ("host", "https://example.atlassian.net".to_owned()),
+            ("user", "jane@example.com".to_owned()),
+            ("token", "secret".to_owned()),
+        ])
+    }
+
+    #[test]
+    fn credentials_are_only_added_when_missing() {
+        let credentials = credentials();
+        let user = |line: &str| {
+            parse(&app, &credentials, &words(line))
+                .map(|v| leaf(&v).value_of("user").map(|v| v.to_owned()))
+                .map_err(|err| err.kind)
+        };
+
+        assert_eq!(user("issues -b 1"), Ok(Some("jane@example.com".to_owned())));
+        assert_eq!(
+            user("issues -b 1 -u john@example.com"),
+            Ok(Some("john@example.com".to_owned()))
+        );
+        assert_eq!(
+            user("issues -b 1 --user=john@example.com"),
+            Ok(Some("john@example.com".to_owned()))
+        );
+        assert_eq!(user("completions"), Err(ErrorKind::MissingRequiredArgument));
+    }
+
+    #[test]
+    fn context_is_added_to_commands_that_take_it() {
+        let credentials = credentials();
+        let parse = |words: &[String]| parse(&app, &credentials, words);
+        let apply = |context: &Context, line: &str| {
+            let mut words = words(line);
+            context.apply(&mut words, parse);
+            words.join(" ")
+        };
+        let context = Context {
+            board: Some("1".to_owned()),
+            sprint: Some("10".to_owned()),
+        };
+
+        assert_eq!(apply(&context, "issues"), "issues --sprint-id 10");
+        assert_eq!(
+            apply(&context, "issues --board-id 2"),
+            "issues --board-id 2"
+        );
+        assert_eq!(apply(&context, "boards view"), "boards view 1");
+        assert_eq!(apply(&context, "boards view 2"), "boards view 2");
+        assert_eq!(apply(&context, "stats"), "stats");
+
+        let context = Context {
+            board: Some("1".to_owned()),
+            sprint: None,
+        };
+        assert_eq!(apply(&context, "issues"), "issues --board-id 1");
+    }
+}
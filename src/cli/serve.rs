@@ -0,0 +1,355 @@
+use super::commands;
+use super::repl::Credentials;
+
+use jira::{Client, Error, Result};
+
+use chrono::Utc;
+use clap::{App, ArgMatches};
+use hmac::{Hmac, Mac, NewMac};
+use log::{info, warn};
+use serde_json::json;
+use sha2::Sha256;
+use tiny_http::{Header, Method, Request, Response, Server};
+use url::form_urlencoded;
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const COMMANDS: &[&str] = &["issues", "report"];
+const RESERVED: &[&str] = &[
+    "organization",
+    "host",
+    "user",
+    "token",
+    "reset",
+    "clear-done-remaining",
+    "rollup-worklogs",
+    "save",
+    "compare",
+];
+const SLACK_USAGE: &str = "Usage: `/sprint report` or `/sprint issues @user`";
+
+struct Slack<'a> {
+    secret: &'a str,
+    boards: Vec<&'a str>,
+}
+
+pub fn run<A>(app: A, client: &Client, options: &ArgMatches) -> Result<()>
+where
+    A: Fn() -> App<'static, 'static>,
+{
+    let listen = options.value_of("listen").unwrap_or("127.0.0.1:8080");
+
+    let token = match options.is_present("api") {
+        true => Some(
+            options
+                .value_of("api-token")
+                .ok_or(Error::Config("api-token".to_owned()))?,
+        ),
+        false => None,
+    };
+
+    let slack = match options.is_present("slack") {
+        true => Some(Slack {
+            secret: options
+                .value_of("slack-secret")
+                .ok_or(Error::Config("slack-secret".to_owned()))?,
+            boards: options
+                .values_of("board")
+                .ok_or(Error::Config("board".to_owned()))?
+                .collect(),
+        }),
+        false => None,
+    };
+
+    let credentials = Credentials::new(options);
+
+    let server = Server::http(listen).map_err(|err| Error::Serve(err.to_string()))?;
+    info!("listening on http://{}", listen);
+
+    for mut request in server.incoming_requests() {
+        let (status, body) = match (&slack, token) {
+            (Some(slack), _) if request.url() == "/slack" => {
+                handle_slack(&app, client, &credentials, slack, &mut request)
+            }
+            (_, Some(token)) if authorized(&request, token) => {
+                handle(&app, client, &credentials, &request)
+            }
+            (_, Some(_)) => (401, json!({ "error": "missing or invalid API token" })),
+            (_, None) => (404, json!({ "error": "not found" })),
+        };
+        info!("{} {} {}", request.method(), request.url(), status);
+
+        let response = Response::from_string(body.to_string())
+            .with_status_code(status)
+            .with_header(
+                Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap(),
+            );
+        if let Err(err) = request.respond(response) {
+            warn!("unable to send response: {}", err);
+        }
+    }
+
+    Ok(())
+}
+
+/// Serves the gauges of `export-metrics` on `GET /metrics`, fetching them
+/// from Jira on every scrape. Scrapers must send the `--api-token` as a
+/// bearer token when one is given.
+pub fn metrics(client: &Client, listen: &str, options: &ArgMatches) -> Result<()> {
+    // Like `serve`, `:9123` only listens on the loopback interface; binding
+    // other interfaces takes an explicit address.
+    let listen = match listen.starts_with(':') {
+        true => format!("127.0.0.1{}", listen),
+        false => listen.to_owned(),
+    };
+    let token = options.value_of("api-token");
+
+    let server = Server::http(&listen).map_err(|err| Error::Serve(err.to_string()))?;
+    info!("serving metrics on http://{}/metrics", listen);
+
+    for request in server.incoming_requests() {
+        let (status, body) = match (request.method(), request.url()) {
+            _ if !token.is_none_or(|v| authorized(&request, v)) => {
+                (401, "missing or invalid API token\n".to_owned())
+            }
+            (Method::Get, "/metrics") => {
+                match client.metrics(&commands::sources(options), Utc::now()) {
+                    Ok(text) => (200, text),
+                    Err(err) => {
+                        warn!("unable to collect metrics: {}", err);
+                        (500, format!("{}\n", err))
+                    }
+                }
+            }
+            _ => (404, "not found\n".to_owned()),
+        };
+        info!("{} {} {}", request.method(), request.url(), status);
+
+        let response = Response::from_string(body)
+            .with_status_code(status)
+            .with_header(
+                Header::from_bytes(&b"Content-Type"[..], &b"text/plain; version=0.0.4"[..])
+                    .unwrap(),
+            );
+        if let Err(err) = request.respond(response) {
+            warn!("unable to send response: {}", err);
+        }
+    }
+
+    Ok(())
+}
+
+fn authorized(request: &Request, token: &str) -> bool {
+    let expected = format!("Bearer {}", token);
+    header(request, "Authorization") == Some(expected.as_str())
+}
+
+fn header<'a>(request: &'a Request, name: &'static str) -> Option<&'a str> {
+    request
+        .headers()
+        .iter()
+        .find(|v| v.field.equiv(name))
+        .map(|v| v.value.as_str())
+}
+
+fn handle<A>(
+    app: &A,
+    client: &Client,
+    credentials: &Credentials,
+    request: &Request,
+) -> (u16, serde_json::Value)
+where
+    A: Fn() -> App<'static, 'static>,
+{
+    if request.method() != &Method::Get {
+        return (405, json!({ "error": "only GET requests are supported" }));
+    }
+
+    let (path, query) = match request.url().find('?') {
+        Some(i) => (&request.url()[..i], &request.url()[i + 1..]),
+        None => (request.url(), ""),
+    };
+
+    let command = path.trim_matches('/');
+    if !COMMANDS.contains(&command) {
+        return (
+            404,
+            json!({ "error": format!("unknown endpoint `{}`", path) }),
+        );
+    }
+
+    let args = match query_args(command, query) {
+        Ok(args) => args,
+        Err(message) => return (400, json!({ "error": message })),
+    };
+
+    let matches = match parse(app, credentials.add_to(args)) {
+        Ok(matches) => matches,
+        Err(message) => return (400, json!({ "error": message })),
+    };
+
+    match commands::json(client, &matches) {
+        Ok(rows) => (200, rows),
+        Err(err) => {
+            let status = match err {
+                Error::Ambiguous(..) | Error::Config(_) | Error::Jql(_) => 400,
+                Error::NotFound(_) => 404,
+                _ => 502,
+            };
+            (status, json!({ "error": err.to_string() }))
+        }
+    }
+}
+
+/// Turns a query into command line arguments, so the API accepts (and
+/// validates) exactly the same options as the CLI, except the reserved ones.
+fn query_args(command: &str, query: &str) -> std::result::Result<Vec<String>, String> {
+    let mut args = vec!["jira".to_owned(), command.to_owned()];
+    for (key, value) in form_urlencoded::parse(query.as_bytes()) {
+        if RESERVED.contains(&key.as_ref()) {
+            return Err(format!("`{}` is not allowed", key));
+        }
+        args.push(format!("--{}", key));
+        if !value.is_empty() && value != "true" {
+            args.push(value.into_owned());
+        }
+    }
+    Ok(args)
+}
+
+fn handle_slack<A>(
+    app: &A,
+    client: &Client,
+    credentials: &Credentials,
+    slack: &Slack,
+    request: &mut Request,
+) -> (u16, serde_json::Value)
+where
+    A: Fn() -> App<'static, 'static>,
+{
+    if request.method() != &Method::Post {
+        return (405, json!({ "error": "only POST requests are supported" }));
+    }
+
+    let mut body = String::new();
+    if request.as_reader().read_to_string(&mut body).is_err() || !verified(slack, request, &body) {
+        return (401, json!({ "error": "invalid Slack signature" }));
+    }
+
+    let text = form_urlencoded::parse(body.as_bytes())
+        .find(|(key, _)| key == "text")
+        .map(|(_, value)| value.into_owned())
+        .unwrap_or_default();
+
+    let mut args = vec!["jira".to_owned()];
+    let mut words = text.split_whitespace();
+    match (words.next(), words.next()) {
+        (Some("report"), None) => args.push("report".to_owned()),
+        (Some("issues"), user) => {
+            args.push("issues".to_owned());
+            if let Some(user) = user {
+                args.push("--assignee".to_owned());
+                args.push(slack_user(user).to_owned());
+            }
+        }
+        _ => return (200, ephemeral(SLACK_USAGE)),
+    }
+    for board in slack.boards.iter() {
+        args.push("--board-id".to_owned());
+        args.push((*board).to_owned());
+    }
+    args.push("--all-active".to_owned());
+
+    let table = parse(app, credentials.add_to(args))
+        .and_then(|matches| commands::table(client, &matches).map_err(|err| err.to_string()));
+
+    match table {
+        Ok((_, table)) if table.is_empty() => (200, ephemeral("No issues were found")),
+        Ok((titles, mut table)) => {
+            table.set_titles(titles);
+            (
+                200,
+                json!({
+                    "response_type": "in_channel",
+                    "text": format!("```\n{}```", table),
+                }),
+            )
+        }
+        Err(message) => (200, ephemeral(&message)),
+    }
+}
+
+/// Checks the signature Slack computes over each request with the app's
+/// signing secret (https://api.slack.com/authentication/verifying-requests-from-slack).
+fn verified(slack: &Slack, request: &Request, body: &str) -> bool {
+    let (timestamp, signature) = match (
+        header(request, "X-Slack-Request-Timestamp"),
+        header(request, "X-Slack-Signature"),
+    ) {
+        (Some(timestamp), Some(signature)) => (timestamp, signature),
+        _ => return false,
+    };
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|v| v.as_secs() as i64)
+        .unwrap_or_default();
+    match timestamp.parse::<i64>() {
+        Ok(timestamp) if (now - timestamp).abs() <= 300 => (),
+        _ => return false,
+    }
+
+    let signature = match signature.strip_prefix("v0=").map(hex::decode) {
+        Some(Ok(signature)) => signature,
+        _ => return false,
+    };
+
+    let mut mac = match Hmac::<Sha256>::new_varkey(slack.secret.as_bytes()) {
+        Ok(mac) => mac,
+        Err(_) => return false,
+    };
+    mac.update(format!("v0:{}:{}", timestamp, body).as_bytes());
+    mac.verify(&signature).is_ok()
+}
+
+/// Slack sends mentions as `<@U024BE7LH|jane.doe>`, or as `@jane.doe` when
+/// escaping is disabled for the command.
+fn slack_user(user: &str) -> &str {
+    let user = user.trim_start_matches('<').trim_end_matches('>');
+    user.rsplit('|')
+        .next()
+        .unwrap_or(user)
+        .trim_start_matches('@')
+}
+
+fn ephemeral(text: &str) -> serde_json::Value {
+    json!({ "response_type": "ephemeral", "text": text })
+}
+
+fn parse<A>(app: &A, args: Vec<String>) -> std::result::Result<ArgMatches<'static>, String>
+where
+    A: Fn() -> App<'static, 'static>,
+{
+    app()
+        .get_matches_from_safe(args)
+        .map_err(|err| err.message.lines().next().unwrap_or_default().to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reserved_options_are_not_allowed() {
+        assert_eq!(
+            query_args("report", "sprint=10&planning=true").unwrap(),
+            ["jira", "report", "--sprint", "10", "--planning"]
+        );
+        for key in RESERVED {
+            assert_eq!(
+                query_args("report", &format!("sprint=10&{}", key)),
+                Err(format!("`{}` is not allowed", key))
+            );
+        }
+    }
+}
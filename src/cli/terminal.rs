@@ -0,0 +1,114 @@
+use jira::output::{json_rows, Output};
+use jira::{layout, markdown, Result};
+
+use chrono::Local;
+use clap::ArgMatches;
+use log::warn;
+use prettytable::{Row, Table};
+
+use std::cell::RefCell;
+use std::io::{self, Write};
+
+/// Prints what the commands output on the terminal, in the format selected
+/// with `--output`.
+pub struct Terminal {
+    width: Option<usize>,
+    wrap: bool,
+    a11y: bool,
+    /// The `--output` and `--title` of the command that runs.
+    selected: RefCell<(Option<String>, Option<String>)>,
+}
+
+impl Terminal {
+    pub fn new(options: &ArgMatches) -> Self {
+        let a11y = options.is_present("a11y");
+
+        let width = match options.value_of("max-width") {
+            _ if a11y || options.is_present("no-truncate") => None,
+            Some(width) => width.parse().ok(),
+            None => term_size::dimensions().map(|(width, _)| width),
+        };
+
+        Terminal {
+            width,
+            wrap: options.is_present("wrap"),
+            a11y,
+            selected: RefCell::new((None, None)),
+        }
+    }
+
+    /// Uses the `--output` and `--title` of a command for the tables it
+    /// outputs.
+    pub fn select(&self, options: &ArgMatches) {
+        *self.selected.borrow_mut() = (
+            options.value_of("output").map(|v| v.to_owned()),
+            options.value_of("title").map(|v| v.to_owned()),
+        );
+    }
+}
+
+impl Output for Terminal {
+    fn table(&self, title: &str, titles: Row, mut table: Table, empty: &str) {
+        let (output, custom) = &*self.selected.borrow();
+        match output.as_deref() {
+            Some("markdown") => {
+                let title = custom.as_deref().unwrap_or(title);
+                let generated = Local::now().format("%F %R").to_string();
+                print!("{}", markdown::document(title, &titles, &table, &generated));
+            }
+            Some("json") => println!("{:#}", json_rows(&titles, &table)),
+            _ if table.is_empty() => println!("{}", empty),
+            _ if self.a11y => {
+                for row in table.row_iter() {
+                    println!(
+                        "{}.",
+                        titles
+                            .iter()
+                            .zip(row.iter())
+                            .map(|(title, cell)| format!(
+                                "{}: {}",
+                                title.get_content(),
+                                cell.get_content().replace('\n', ", ")
+                            ))
+                            .collect::<Vec<String>>()
+                            .join(". ")
+                    );
+                }
+            }
+            _ => {
+                if let Some(width) = self.width {
+                    layout::fit(&titles, &mut table, width, self.wrap);
+                }
+                table.set_titles(titles);
+                println!();
+                table.printstd();
+                println!();
+            }
+        }
+    }
+
+    fn write(&self, bytes: &[u8]) {
+        let mut stdout = io::stdout();
+        if let Err(err) = stdout.write_all(bytes).and_then(|_| stdout.flush()) {
+            warn!("unable to write the output: {}", err);
+        }
+    }
+
+    fn message(&self, message: &str) {
+        println!("{}", message);
+    }
+
+    fn note(&self, note: &str) {
+        eprintln!("{}", note);
+    }
+
+    /// Asks on the terminal, where anything but yes means no.
+    fn confirm(&self, question: &str) -> Result<bool> {
+        eprint!("{} [y/N] ", question);
+        io::stderr().flush()?;
+
+        let mut answer = String::new();
+        io::stdin().read_line(&mut answer)?;
+        Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{Error, Result};
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Puts text on the system clipboard, using the first clipboard tool of the
+/// platform that is available.
+pub fn copy(text: &str) -> Result<()> {
+    let wayland = std::env::var_os("WAYLAND_DISPLAY").is_some();
+    let mut errors = Vec::new();
+    for (program, args) in commands(wayland) {
+        match run(program, args, text) {
+            Ok(()) => return Ok(()),
+            Err(err) => errors.push(format!("{}: {}", program, err)),
+        }
+    }
+    Err(Error::Clipboard(errors.join(", ")))
+}
+
+/// Returns the clipboard tools to try, in order.
+fn commands(wayland: bool) -> Vec<(&'static str, &'static [&'static str])> {
+    if cfg!(target_os = "macos") {
+        return vec![("pbcopy", &[])];
+    }
+    if cfg!(windows) {
+        return vec![("clip", &[])];
+    }
+
+    let mut commands: Vec<(&str, &[&str])> = vec![
+        ("xclip", &["-selection", "clipboard"]),
+        ("xsel", &["--clipboard", "--input"]),
+    ];
+    if wayland {
+        commands.insert(0, ("wl-copy", &[]));
+    }
+    commands
+}
+
+fn run(program: &str, args: &[&str], text: &str) -> std::io::Result<()> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+
+    let status = child.wait()?;
+    match status.success() {
+        true => Ok(()),
+        false => Err(std::io::Error::other(format!("exited with {}", status))),
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wayland_is_tried_before_x11() {
+        let programs = |wayland| {
+            commands(wayland)
+                .into_iter()
+                .map(|(program, _)| program)
+                .collect::<Vec<&str>>()
+        };
+        assert_eq!(programs(false), ["xclip", "xsel"]);
+        assert_eq!(programs(true), ["wl-copy", "xclip", "xsel"]);
+    }
+}
@@ -0,0 +1,169 @@
+use crate::duration::{Durations, Rounding};
+use crate::{shell, Result};
+
+use serde::de::{self, Deserializer};
+use serde::Deserialize;
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct Config {
+    pub boards: Vec<u64>,
+    /// Estimates above this many days are flagged before they are written.
+    pub max_estimate_days: Option<f64>,
+    /// Statuses in the order they are shown when grouping by status, instead
+    /// of the column order of the board.
+    pub status_order: Vec<String>,
+    /// Multi-user custom field (e.g. `customfield_10050`) with the people
+    /// an issue is worked on together with its assignee.
+    pub co_assignees_field: Option<String>,
+    /// Date field used to order epics (defaults to the due date).
+    pub target_date_field: Option<String>,
+    /// Select field with the T-shirt size of an issue, for teams that size
+    /// issues instead of estimating them.
+    pub size_field: Option<String>,
+    /// Representative hours per T-shirt size, e.g. `M = 16`.
+    pub sizes: BTreeMap<String, f64>,
+    /// Length of a working day and week, as configured in the time tracking
+    /// settings of Jira (8 hours and 5 days by default).
+    #[serde(deserialize_with = "hours_per_day")]
+    pub hours_per_day: Option<f64>,
+    #[serde(deserialize_with = "days_per_week")]
+    pub days_per_week: Option<u64>,
+    /// How estimates are rounded to whole minutes when they are written:
+    /// `nearest` (the default), `up` or `down`.
+    pub estimate_rounding: Rounding,
+    /// Checkbox field Jira flags impediments with (defaults to
+    /// `customfield_10021`).
+    pub flagged_field: Option<String>,
+    /// Aliases for custom fields shown with `issues --field`, e.g.
+    /// `team = "customfield_10045"`.
+    pub fields: BTreeMap<String, String>,
+    /// Named command lines, e.g. `alias.standup = "issues -b 42 --no-subtasks"`.
+    pub alias: BTreeMap<String, String>,
+    /// Keep track of which commands are run, for `jira stats`. Nothing is
+    /// sent anywhere.
+    pub stats: bool,
+    /// Record the totals of every report and the metrics of its sprints in
+    /// a local SQLite database, for `jira history` (needs the `history`
+    /// feature).
+    pub history: bool,
+    /// Shell commands to run after a command succeeds, per command, e.g.
+    /// `hooks.report = ["./post-to-chat.sh"]` or `hooks."sprints health"`.
+    /// They get the tables the command printed as JSON on stdin.
+    pub hooks: BTreeMap<String, Vec<String>>,
+}
+
+impl Config {
+    pub fn load() -> Result<Self> {
+        let path = match Self::path() {
+            Some(path) if path.exists() => path,
+            _ => return Ok(Default::default()),
+        };
+
+        Ok(toml::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Reads and shows durations using the configured working day and week.
+    pub fn durations(&self) -> Durations {
+        Durations::new(
+            self.hours_per_day.unwrap_or(8.0),
+            self.days_per_week.unwrap_or(5),
+        )
+    }
+
+    /// Expands an alias used as the command, keeping the arguments given
+    /// after it. The program name is the first argument, as in `env::args`.
+    pub fn expand_alias(&self, args: &[String]) -> Option<Vec<String>> {
+        let words = shell::split(self.alias.get(args.get(1)?)?)?;
+        Some(
+            args[..1]
+                .iter()
+                .cloned()
+                .chain(words)
+                .chain(args[2..].iter().cloned())
+                .collect(),
+        )
+    }
+
+    pub fn path() -> Option<PathBuf> {
+        match env::var_os("JIRA_CONFIG") {
+            Some(path) => Some(PathBuf::from(path)),
+            None => dirs::config_dir().map(|v| v.join("jira").join("config.toml")),
+        }
+    }
+}
+
+fn hours_per_day<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<f64>, D::Error> {
+    match Option::<f64>::deserialize(deserializer)? {
+        Some(hours) if !(hours > 0.0 && hours <= 24.0) => Err(de::Error::custom(
+            "hours_per_day must be more than 0 and at most 24",
+        )),
+        hours => Ok(hours),
+    }
+}
+
+fn days_per_week<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<u64>, D::Error> {
+    match Option::<u64>::deserialize(deserializer)? {
+        Some(days) if !(1..=7).contains(&days) => {
+            Err(de::Error::custom("days_per_week must be between 1 and 7"))
+        }
+        days => Ok(days),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aliases_are_expanded_with_the_remaining_arguments() {
+        let config: Config = toml::from_str(
+            "[alias]\n\
+             standup = \"issues -b 42 --no-subtasks --title 'Daily standup'\"\n",
+        )
+        .unwrap();
+        let args = |v: &[&str]| v.iter().map(|v| v.to_string()).collect::<Vec<String>>();
+
+        assert_eq!(
+            config.expand_alias(&args(&["jira", "standup", "-a", "me"])),
+            Some(args(&[
+                "jira",
+                "issues",
+                "-b",
+                "42",
+                "--no-subtasks",
+                "--title",
+                "Daily standup",
+                "-a",
+                "me"
+            ]))
+        );
+        assert_eq!(config.expand_alias(&args(&["jira", "report"])), None);
+        assert_eq!(config.expand_alias(&args(&["jira"])), None);
+    }
+
+    #[test]
+    fn working_days_must_have_a_length() {
+        for toml in [
+            "hours_per_day = 0.0",
+            "hours_per_day = -8.0",
+            "days_per_week = 0",
+        ]
+        .iter()
+        {
+            assert!(toml::from_str::<Config>(toml).is_err(), "{}", toml);
+        }
+
+        let config: Config = toml::from_str("hours_per_day = 7.5\ndays_per_week = 4").unwrap();
+        assert_eq!(config.durations().day(), 27000);
+    }
+}
@@ -0,0 +1,144 @@
+use crate::cards::escape;
+use crate::{Error, Result};
+
+use prettytable::{Row, Table};
+use reqwest::blocking::{Client, RequestBuilder};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// A page as returned by the Confluence content API.
+#[derive(Deserialize, Debug)]
+pub struct Page {
+    pub id: String,
+    pub title: String,
+    pub version: Version,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Version {
+    pub number: u64,
+}
+
+#[derive(Deserialize, Debug)]
+struct Pages {
+    results: Vec<Page>,
+}
+
+pub struct Confluence {
+    url: String,
+    user: String,
+    token: String,
+    client: Client,
+}
+
+impl Confluence {
+    pub fn new(url: &str, user: &str, token: &str, client: Client) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_owned(),
+            user: user.to_owned(),
+            token: token.to_owned(),
+            client,
+        }
+    }
+
+    /// Creates the page with the given title in a space, or replaces the body
+    /// of that page when it already exists. Returns the page and whether it
+    /// was created.
+    pub fn publish(&self, space: &str, title: &str, body: &str) -> Result<(Page, bool)> {
+        let mut content = json!({
+            "type": "page",
+            "title": title,
+            "space": { "key": space },
+            "body": { "storage": { "value": body, "representation": "storage" } },
+        });
+
+        match self.find(space, title)? {
+            Some(page) => {
+                content["version"] = json!({ "number": page.version.number + 1 });
+                let request = self
+                    .client
+                    .put(&format!("{}/rest/api/content/{}", self.url, page.id));
+                Ok((self.send(request.json(&content))?, false))
+            }
+            None => {
+                let request = self.client.post(&format!("{}/rest/api/content", self.url));
+                Ok((self.send(request.json(&content))?, true))
+            }
+        }
+    }
+
+    /// Returns the URL of a page in the Confluence web UI.
+    pub fn page_url(&self, page: &Page) -> String {
+        format!("{}/pages/viewpage.action?pageId={}", self.url, page.id)
+    }
+
+    fn find(&self, space: &str, title: &str) -> Result<Option<Page>> {
+        let request = self
+            .client
+            .get(&format!("{}/rest/api/content", self.url))
+            .query(&[("spaceKey", space), ("title", title), ("expand", "version")]);
+        let pages: Pages = self.send(request)?;
+        Ok(pages.results.into_iter().next())
+    }
+
+    fn send<T: serde::de::DeserializeOwned>(&self, request: RequestBuilder) -> Result<T> {
+        let response = request
+            .basic_auth(&self.user, Some(&self.token))
+            .send()
+            .map_err(|err| Error::Confluence(err.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body: Value = response.json().unwrap_or_default();
+            let message = body["message"].as_str().unwrap_or_else(|| status.as_str());
+            return Err(Error::Confluence(message.to_owned()));
+        }
+
+        response
+            .json()
+            .map_err(|err| Error::Confluence(err.to_string()))
+    }
+}
+
+/// Renders a table in the Confluence storage format (a subset of XHTML).
+pub fn storage(titles: &Row, table: &Table, generated: &str) -> String {
+    let mut output = String::from("<table><tbody><tr>");
+    for cell in titles.iter() {
+        output.push_str(&format!("<th>{}</th>", escape(&cell.get_content())));
+    }
+    output.push_str("</tr>");
+    for row in table.row_iter() {
+        output.push_str("<tr>");
+        for cell in row.iter() {
+            let content = escape(&cell.get_content()).replace('\n', "<br />");
+            output.push_str(&format!("<td>{}</td>", content));
+        }
+        output.push_str("</tr>");
+    }
+    output.push_str("</tbody></table>");
+    output.push_str(&format!(
+        "<p><em>Generated at {}</em></p>",
+        escape(generated)
+    ));
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use prettytable::row;
+
+    #[test]
+    fn tables_are_rendered_as_storage_format() {
+        let mut table = Table::new();
+        table.add_row(row!["Jane & John", "Sprint 9\nSprint 10"]);
+
+        assert_eq!(
+            storage(&row!["Assignee", "Sprints"], &table, "2020-06-01 09:00"),
+            "<table><tbody><tr><th>Assignee</th><th>Sprints</th></tr>\
+             <tr><td>Jane &amp; John</td><td>Sprint 9<br />Sprint 10</td></tr></tbody></table>\
+             <p><em>Generated at 2020-06-01 09:00</em></p>"
+        );
+    }
+}
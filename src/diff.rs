@@ -0,0 +1,79 @@
+/// The old and new values of a single field of an issue.
+pub struct Change {
+    pub field: String,
+    pub old: Vec<String>,
+    pub new: Vec<String>,
+}
+
+impl Change {
+    pub fn new(field: &str, old: Vec<String>, new: Vec<String>) -> Self {
+        Self {
+            field: field.to_owned(),
+            old,
+            new,
+        }
+    }
+}
+
+/// Renders the changes to an issue in unified diff style, with a hunk per
+/// changed field. Returns an empty string when nothing changes.
+pub fn unified(issue_key: &str, changes: &[Change]) -> String {
+    let mut output = String::new();
+
+    for change in changes.iter().filter(|v| v.old != v.new) {
+        output.push_str(&format!("@@ {} @@\n", change.field));
+        for value in change.old.iter() {
+            match change.new.contains(value) {
+                true => output.push_str(&format!(" {}\n", value)),
+                false => output.push_str(&format!("-{}\n", value)),
+            }
+        }
+        for value in change.new.iter().filter(|v| !change.old.contains(v)) {
+            output.push_str(&format!("+{}\n", value));
+        }
+    }
+
+    match output.is_empty() {
+        true => output,
+        false => format!("--- a/{0}\n+++ b/{0}\n{1}", issue_key, output),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn changed_fields_are_shown_as_hunks() {
+        let changes = vec![
+            Change::new(
+                "labels",
+                strings(&["backend", "tech-debt"]),
+                strings(&["backend", "infra"]),
+            ),
+            Change::new("components", strings(&["API"]), strings(&["API"])),
+            Change::new("remaining", strings(&["0.5d"]), strings(&["1.0d"])),
+        ];
+
+        assert_eq!(
+            unified("ABC-1", &changes),
+            "--- a/ABC-1\n+++ b/ABC-1\n\
+             @@ labels @@\n backend\n-tech-debt\n+infra\n\
+             @@ remaining @@\n-0.5d\n+1.0d\n"
+        );
+    }
+
+    #[test]
+    fn unchanged_issues_render_nothing() {
+        let changes = vec![Change::new(
+            "labels",
+            strings(&["infra"]),
+            strings(&["infra"]),
+        )];
+        assert_eq!(unified("ABC-1", &changes), "");
+    }
+}
@@ -0,0 +1,255 @@
+use serde::Deserialize;
+
+use std::str::FromStr;
+
+/// How durations are shown by `Durations::display`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Style {
+    /// Working days with one decimal, like `1.5d`.
+    Days,
+    /// Hours with one decimal, like `12.0h`.
+    Hours,
+    /// Jira durations, like `1d 4h`.
+    Jira,
+    Seconds,
+}
+
+impl FromStr for Style {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "days" => Ok(Style::Days),
+            "hours" => Ok(Style::Hours),
+            "jira" => Ok(Style::Jira),
+            "seconds" => Ok(Style::Seconds),
+            _ => Err(format!("unknown duration format `{}`", s)),
+        }
+    }
+}
+
+/// Reads and shows durations using the length of a working day and week,
+/// which should match the time tracking settings of the Jira instance (8
+/// hours and 5 days by default), in the style chosen with `--duration-format`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Durations {
+    day: u64,
+    week: u64,
+    style: Option<Style>,
+}
+
+impl Default for Durations {
+    fn default() -> Self {
+        Durations::new(8.0, 5)
+    }
+}
+
+impl Durations {
+    /// A working day is at least a minute long, so formatting never divides
+    /// by zero; `Config::load` rejects days that aren't positive.
+    pub fn new(hours_per_day: f64, days_per_week: u64) -> Self {
+        let day = ((hours_per_day * 60.0).round() as u64).max(1) * 60;
+        Durations {
+            day,
+            week: day * days_per_week,
+            style: None,
+        }
+    }
+
+    /// Shows all durations in this style, instead of the default of each view.
+    pub fn style(mut self, style: Option<Style>) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Returns the seconds in a working day.
+    pub fn day(&self) -> u64 {
+        self.day
+    }
+
+    /// Converts seconds into working days.
+    pub fn days(&self, seconds: u64) -> f64 {
+        seconds as f64 / self.day as f64
+    }
+
+    /// Formats seconds for display in the chosen style, or as working days.
+    pub fn display(&self, seconds: u64) -> String {
+        self.display_or(seconds, Style::Days)
+    }
+
+    /// Formats seconds for display in the chosen style, or in `default`, for
+    /// views that show the estimates of single issues as Jira does.
+    pub fn display_or(&self, seconds: u64, default: Style) -> String {
+        display_as(seconds, self.style.unwrap_or(default), self.day)
+    }
+
+    /// Formats seconds as a Jira duration like `2d 4h 30m`, rounded to whole
+    /// minutes.
+    pub fn format(&self, seconds: u64, rounding: Rounding) -> String {
+        format_with(seconds, rounding, self.day)
+    }
+
+    /// Parses a Jira duration like `2d`, `1d4h`, `1w 2d` or `1.5h` into
+    /// seconds. A bare number is taken as minutes, as Jira does.
+    pub fn parse(&self, input: &str) -> Option<u64> {
+        parse_with(input, self.day, self.week)
+    }
+}
+
+fn display_as(seconds: u64, style: Style, day: u64) -> String {
+    match style {
+        Style::Days => format!("{:.1}d", seconds as f64 / day as f64),
+        Style::Hours => format!("{:.1}h", seconds as f64 / 3600.0),
+        Style::Jira => format_with(seconds, Rounding::Nearest, day),
+        Style::Seconds => seconds.to_string(),
+    }
+}
+
+/// How durations are rounded to the whole minutes Jira stores.
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Rounding {
+    #[default]
+    Nearest,
+    Up,
+    Down,
+}
+
+fn format_with(seconds: u64, rounding: Rounding, day: u64) -> String {
+    let minutes = match rounding {
+        Rounding::Nearest => (seconds + 30) / 60,
+        Rounding::Up => seconds.div_ceil(60),
+        Rounding::Down => seconds / 60,
+    };
+    let minutes_per_day = day / 60;
+
+    let parts: Vec<String> = [
+        (minutes / minutes_per_day, 'd'),
+        (minutes % minutes_per_day / 60, 'h'),
+        (minutes % minutes_per_day % 60, 'm'),
+    ]
+    .iter()
+    .filter(|(value, _)| *value > 0)
+    .map(|(value, unit)| format!("{}{}", value, unit))
+    .collect();
+
+    match parts.is_empty() {
+        true => "0m".to_owned(),
+        false => parts.join(" "),
+    }
+}
+
+fn parse_with(input: &str, day: u64, week: u64) -> Option<u64> {
+    let input = input.trim();
+    if let Ok(minutes) = input.parse::<f64>() {
+        return Some((minutes * 60.0).round() as u64);
+    }
+
+    let (mut seconds, mut number, mut units) = (0.0, String::new(), 0);
+    for c in input.chars() {
+        let unit = match c {
+            '0'..='9' | '.' => {
+                number.push(c);
+                continue;
+            }
+            c if c.is_whitespace() && number.is_empty() => continue,
+            'w' => week as f64,
+            'd' => day as f64,
+            'h' => 60.0 * 60.0,
+            'm' => 60.0,
+            _ => return None,
+        };
+        seconds += number.parse::<f64>().ok()? * unit;
+        number.clear();
+        units += 1;
+    }
+
+    match number.is_empty() && units > 0 {
+        true => Some(seconds.round() as u64),
+        false => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Option<u64> {
+        Durations::default().parse(input)
+    }
+
+    fn format(seconds: u64, rounding: Rounding) -> String {
+        Durations::default().format(seconds, rounding)
+    }
+
+    #[test]
+    fn durations_are_parsed_into_seconds() {
+        assert_eq!(parse("2d"), Some(2 * 8 * 3600));
+        assert_eq!(parse("1d4h"), Some(12 * 3600));
+        assert_eq!(parse("1w 2d 30m"), Some(7 * 8 * 3600 + 1800));
+        assert_eq!(parse("1.5h"), Some(5400));
+        assert_eq!(parse("90"), Some(5400));
+        assert_eq!(parse(""), None);
+        assert_eq!(parse("4"), Some(240));
+        assert_eq!(parse("1d 4"), None);
+        assert_eq!(parse("2 days"), None);
+    }
+
+    #[test]
+    fn durations_are_formatted_in_whole_minutes() {
+        assert_eq!(format(0, Rounding::Nearest), "0m");
+        assert_eq!(
+            format(2 * 8 * 3600 + 4 * 3600 + 1800, Rounding::Nearest),
+            "2d 4h 30m"
+        );
+        assert_eq!(format(8 * 3600 + 60, Rounding::Nearest), "1d 1m");
+        assert_eq!(format(89, Rounding::Nearest), "1m");
+        assert_eq!(format(90, Rounding::Nearest), "2m");
+        assert_eq!(format(61, Rounding::Up), "2m");
+        assert_eq!(format(119, Rounding::Down), "1m");
+        assert_eq!(format(29, Rounding::Nearest), "0m");
+
+        for seconds in [60, 5400, 12 * 3600, 7 * 8 * 3600 + 1800].iter() {
+            assert_eq!(parse(&format(*seconds, Rounding::Nearest)), Some(*seconds));
+        }
+    }
+
+    #[test]
+    fn durations_are_displayed_in_each_style() {
+        let seconds = 12 * 3600;
+        assert_eq!(display_as(seconds, Style::Days, 28800), "1.5d");
+        assert_eq!(display_as(seconds, Style::Days, 27000), "1.6d");
+        assert_eq!(display_as(seconds, Style::Hours, 28800), "12.0h");
+        assert_eq!(display_as(seconds, Style::Jira, 28800), "1d 4h");
+        assert_eq!(display_as(seconds, Style::Seconds, 28800), "43200");
+        assert_eq!("jira".parse(), Ok(Style::Jira));
+        assert!("weeks".parse::<Style>().is_err());
+    }
+
+    #[test]
+    fn durations_use_the_length_of_a_working_day() {
+        let (day, week) = (27000, 4 * 27000);
+        assert_eq!(parse_with("1d", day, week), Some(27000));
+        assert_eq!(parse_with("1w 1h", day, week), Some(4 * 27000 + 3600));
+        assert_eq!(format_with(27000, Rounding::Nearest, day), "1d");
+        assert_eq!(format_with(8 * 3600, Rounding::Nearest, day), "1d 30m");
+        assert_eq!(format_with(2 * 27000, Rounding::Nearest, day), "2d");
+        assert_eq!(Durations::new(7.5, 4).parse("1w"), Some(week));
+    }
+
+    #[test]
+    fn durations_are_displayed_in_the_chosen_style() {
+        let durations = Durations::default();
+        assert_eq!(durations.display(4 * 3600), "0.5d");
+        assert_eq!(durations.display_or(4 * 3600, Style::Jira), "4h");
+
+        let durations = durations.style(Some(Style::Hours));
+        assert_eq!(durations.display(4 * 3600), "4.0h");
+        assert_eq!(durations.display_or(4 * 3600, Style::Jira), "4.0h");
+    }
+
+    #[test]
+    fn working_days_are_at_least_a_minute() {
+        assert_eq!(Durations::new(0.0, 5).format(120, Rounding::Nearest), "2d");
+    }
+}
@@ -3,8 +3,184 @@ use thiserror::Error;
 #[derive(Error, Debug)]
 pub enum Error {
     #[error(transparent)]
-    Jira(#[from] goji::Error),
+    Jira(goji::Error),
+
+    #[error("authentication failed (hint: check JIRA_USER and JIRA_TOKEN)")]
+    Unauthorized,
+
+    #[error("permission denied: {0} (hint: check that your account has access to this board or project)")]
+    Forbidden(String),
+
+    #[error("not found: {0} (hint: check the board, sprint or issue you requested)")]
+    NotFound(String),
+
+    #[error("rate limited by Jira: {0} (hint: wait a minute and try again)")]
+    RateLimited(String),
+
+    #[error("invalid JQL query: {0} (hint: run with -v to see the query that was sent)")]
+    Jql(String),
+
+    #[error("`{0}` matches multiple users: {1} (hint: use the full name, an email address or an account ID)")]
+    Ambiguous(String, String),
 
     #[error("missing required argument `{0}`")]
     Config(String),
+
+    #[error("`{0}` is not set (hint: set `{0}` in the config file to use {1})")]
+    Setting(String, String),
+
+    #[error("cancelled after {0}")]
+    Cancelled(String),
+
+    #[error("check failed: {0}")]
+    Check(String),
+
+    #[error("a timer is already running for {0} (hint: stop it with `jira timer stop`)")]
+    TimerRunning(String),
+
+    #[error("no timer is running (hint: start one with `jira timer start <ISSUE>`)")]
+    NoTimer,
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    #[error("invalid config file: {0}")]
+    Toml(#[from] toml::de::Error),
+
+    #[error("invalid YAML file: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+
+    #[error("invalid CSV file: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("unable to write cache: {0}")]
+    Cache(#[from] toml::ser::Error),
+
+    #[error("unable to render QR code: {0}")]
+    Qr(#[from] qrcode::types::QrError),
+
+    #[error("unable to render PDF: {0}")]
+    Pdf(#[from] printpdf::Error),
+
+    #[error("unable to send notification: {0}")]
+    Webhook(#[from] reqwest::Error),
+
+    #[error("unable to import: {0}")]
+    Import(String),
+
+    #[error("unable to transfer attachment: {0}")]
+    Attachment(String),
+
+    #[error("unable to edit issues: {0}")]
+    Edit(String),
+
+    #[error("unable to publish to Confluence: {0}")]
+    Confluence(String),
+
+    #[error("unable to read or write Tempo worklogs: {0}")]
+    Tempo(String),
+
+    #[error("unable to start API server: {0}")]
+    Serve(String),
+
+    #[error("unable to open a browser: {0} (hint: open the URL yourself)")]
+    Browser(String),
+
+    #[error("unable to copy to the clipboard: {0} (hint: install wl-copy, xclip or xsel)")]
+    Clipboard(String),
+
+    #[error("unable to use the history database: {0}")]
+    History(String),
+
+    #[error("unable to set up the HTTP client: {0}")]
+    Http(String),
+}
+
+impl Error {
+    /// Describes the error for scripts, e.g.
+    /// `{"error": {"kind": "RateLimited", "status": 429, "message": "..."}}`.
+    pub fn json(&self) -> serde_json::Value {
+        let (kind, status) = match self {
+            Error::Jira(goji::Error::Fault { code, .. }) => ("Jira", Some(code.as_u16())),
+            Error::Jira(_) => ("Jira", None),
+            Error::Unauthorized => ("Unauthorized", Some(401)),
+            Error::Forbidden(_) => ("Forbidden", Some(403)),
+            Error::NotFound(_) => ("NotFound", Some(404)),
+            Error::RateLimited(_) => ("RateLimited", Some(429)),
+            Error::Jql(_) => ("Jql", Some(400)),
+            Error::Ambiguous(..) => ("Ambiguous", None),
+            Error::Config(_) => ("Config", None),
+            Error::Setting(..) => ("Setting", None),
+            Error::Cancelled(_) => ("Cancelled", None),
+            Error::Check(_) => ("Check", None),
+            Error::TimerRunning(_) => ("TimerRunning", None),
+            Error::NoTimer => ("NoTimer", None),
+            Error::Io(_) => ("Io", None),
+            Error::Json(_) => ("Json", None),
+            Error::Toml(_) => ("Toml", None),
+            Error::Yaml(_) => ("Yaml", None),
+            Error::Csv(_) => ("Csv", None),
+            Error::Cache(_) => ("Cache", None),
+            Error::Qr(_) => ("Qr", None),
+            Error::Pdf(_) => ("Pdf", None),
+            Error::Webhook(err) => ("Webhook", err.status().map(|v| v.as_u16())),
+            Error::Import(_) => ("Import", None),
+            Error::Attachment(_) => ("Attachment", None),
+            Error::Edit(_) => ("Edit", None),
+            Error::Confluence(_) => ("Confluence", None),
+            Error::Tempo(_) => ("Tempo", None),
+            Error::Serve(_) => ("Serve", None),
+            Error::Browser(_) => ("Browser", None),
+            Error::Clipboard(_) => ("Clipboard", None),
+            Error::History(_) => ("History", None),
+            Error::Http(_) => ("Http", None),
+        };
+        serde_json::json!({
+            "error": {
+                "kind": kind,
+                "status": status,
+                "message": self.to_string(),
+            }
+        })
+    }
+
+    /// Converts errors returned by a search, where a bad request means the JQL was invalid.
+    pub fn jql(err: goji::Error) -> Self {
+        match err {
+            goji::Error::Fault { code, errors } if code.as_u16() == 400 => {
+                Error::Jql(messages(&errors))
+            }
+            err => err.into(),
+        }
+    }
+}
+
+impl From<goji::Error> for Error {
+    fn from(err: goji::Error) -> Self {
+        match err {
+            goji::Error::Unauthorized => Error::Unauthorized,
+            goji::Error::NotFound => Error::NotFound("resource does not exist".to_owned()),
+            goji::Error::Fault { code, errors } => match code.as_u16() {
+                401 => Error::Unauthorized,
+                403 => Error::Forbidden(messages(&errors)),
+                404 => Error::NotFound(messages(&errors)),
+                429 => Error::RateLimited(messages(&errors)),
+                _ => Error::Jira(goji::Error::Fault { code, errors }),
+            },
+            err => Error::Jira(err),
+        }
+    }
+}
+
+fn messages(errors: &goji::Errors) -> String {
+    errors
+        .error_messages
+        .iter()
+        .chain(errors.errors.values())
+        .cloned()
+        .collect::<Vec<String>>()
+        .join("; ")
 }
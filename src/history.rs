@@ -0,0 +1,278 @@
+use crate::client::DEFAULT_TABLE_FORMAT;
+use crate::duration::Durations;
+use crate::snapshot::{self, Snapshot, SprintMetrics, Totals};
+use crate::{Error, Result};
+
+use chrono::{Duration, Utc};
+use prettytable::{row, Row, Table};
+use rusqlite::{params, Connection};
+
+use std::fs;
+use std::path::PathBuf;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS reports (
+        taken TEXT NOT NULL,
+        source TEXT NOT NULL,
+        assignee TEXT NOT NULL,
+        issues INTEGER NOT NULL,
+        points REAL NOT NULL,
+        estimated INTEGER NOT NULL,
+        remaining INTEGER NOT NULL,
+        spent INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS reports_taken ON reports (taken);
+    CREATE TABLE IF NOT EXISTS sprints (
+        taken TEXT NOT NULL,
+        sprint_id INTEGER NOT NULL,
+        name TEXT NOT NULL,
+        committed INTEGER NOT NULL,
+        completed INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS sprints_sprint_id ON sprints (sprint_id, taken);
+";
+
+/// Report snapshots and sprint metrics of earlier runs, kept in a local
+/// SQLite database when `history = true` is set in the config file.
+pub struct History {
+    conn: Connection,
+}
+
+impl History {
+    pub fn open() -> Result<Self> {
+        let path = Self::path().ok_or(Error::History("no data directory was found".to_owned()))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        Self::with_connection(Connection::open(path).map_err(sql)?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA).map_err(sql)?;
+        Ok(Self { conn })
+    }
+
+    pub fn path() -> Option<PathBuf> {
+        dirs::data_dir().map(|v| v.join("jira").join("history.sqlite"))
+    }
+
+    /// Adds the totals per assignee of a report, and the metrics of the
+    /// sprints it was run for.
+    pub fn record(
+        &mut self,
+        source: &str,
+        snapshot: &Snapshot,
+        sprints: &[SprintMetrics],
+    ) -> Result<()> {
+        let tx = self.conn.transaction().map_err(sql)?;
+        for (assignee, totals) in snapshot.assignees.iter() {
+            tx.execute(
+                "INSERT INTO reports (taken, source, assignee, issues, points, estimated, remaining, spent)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    snapshot.taken,
+                    source,
+                    assignee,
+                    totals.issues,
+                    totals.points,
+                    totals.estimated as i64,
+                    totals.remaining as i64,
+                    totals.spent as i64
+                ],
+            )
+            .map_err(sql)?;
+        }
+        for sprint in sprints {
+            tx.execute(
+                "INSERT INTO sprints (taken, sprint_id, name, committed, completed)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    snapshot.taken,
+                    sprint.sprint_id as i64,
+                    sprint.name,
+                    sprint.committed as i64,
+                    sprint.completed as i64
+                ],
+            )
+            .map_err(sql)?;
+        }
+        tx.commit().map_err(sql)
+    }
+
+    /// Lists the recorded sprints in the order they were first recorded,
+    /// with the work committed to when first and last recorded (so scope
+    /// changes show up) and the work completed when last recorded.
+    pub fn velocity_table(&self, durations: &Durations) -> Result<(Row, Table)> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT s.name, COUNT(*), MAX(s.taken),
+                    (SELECT committed FROM sprints f WHERE f.sprint_id = s.sprint_id ORDER BY f.taken LIMIT 1),
+                    (SELECT committed FROM sprints l WHERE l.sprint_id = s.sprint_id ORDER BY l.taken DESC LIMIT 1),
+                    (SELECT completed FROM sprints l WHERE l.sprint_id = s.sprint_id ORDER BY l.taken DESC LIMIT 1)
+                 FROM sprints s
+                 GROUP BY s.sprint_id
+                 ORDER BY MIN(s.taken)",
+            )
+            .map_err(sql)?;
+        let rows = stmt
+            .query_map(params![], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, i64>(3)?,
+                    row.get::<_, i64>(4)?,
+                    row.get::<_, i64>(5)?,
+                ))
+            })
+            .map_err(sql)?;
+
+        let mut table = Table::new();
+        table.set_format(*DEFAULT_TABLE_FORMAT);
+        let titles = row![
+            "Sprint",
+            "Runs",
+            "Last Run",
+            "Committed",
+            "Scope Change",
+            "Completed"
+        ];
+
+        for row in rows {
+            let (name, runs, last, first_committed, committed, completed) = row.map_err(sql)?;
+            table.add_row(row![
+                name,
+                runs,
+                day(&last),
+                durations.display(committed as u64),
+                snapshot::duration_delta(committed as u64, first_committed as u64, durations),
+                durations.display(completed as u64)
+            ]);
+        }
+
+        Ok((titles, table))
+    }
+
+    /// Lists the totals per assignee of the last report of each day, for the
+    /// assignees matching a part of a name.
+    pub fn load_table(
+        &self,
+        assignee: Option<&str>,
+        days: i64,
+        durations: &Durations,
+    ) -> Result<(Row, Table)> {
+        let since = (Utc::now() - Duration::days(days)).to_rfc3339();
+        let assignee = format!("%{}%", assignee.unwrap_or_default());
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT r.taken, r.source, r.assignee, r.issues, r.points, r.estimated, r.remaining, r.spent
+                 FROM reports r
+                 WHERE r.taken >= ?1 AND r.assignee LIKE ?2
+                   AND r.taken = (
+                       SELECT MAX(d.taken) FROM reports d
+                       WHERE d.source = r.source AND substr(d.taken, 1, 10) = substr(r.taken, 1, 10)
+                   )
+                 ORDER BY r.source, r.assignee, r.taken",
+            )
+            .map_err(sql)?;
+        let rows = stmt
+            .query_map(params![since, assignee], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    Totals {
+                        issues: row.get(3)?,
+                        points: row.get(4)?,
+                        estimated: row.get::<_, i64>(5)? as u64,
+                        remaining: row.get::<_, i64>(6)? as u64,
+                        spent: row.get::<_, i64>(7)? as u64,
+                    },
+                ))
+            })
+            .map_err(sql)?;
+
+        let mut table = Table::new();
+        table.set_format(*DEFAULT_TABLE_FORMAT);
+        let titles = row![
+            "Source",
+            "Assignee",
+            "Day",
+            "Issues",
+            "Estimated",
+            "Remaining",
+            "Time Spent"
+        ];
+
+        for row in rows {
+            let (taken, source, assignee, totals) = row.map_err(sql)?;
+            table.add_row(row![
+                source,
+                assignee,
+                day(&taken),
+                totals.issues,
+                durations.display(totals.estimated),
+                durations.display(totals.remaining),
+                durations.display(totals.spent)
+            ]);
+        }
+
+        Ok((titles, table))
+    }
+}
+
+/// Returns the day of an RFC 3339 timestamp.
+fn day(taken: &str) -> &str {
+    taken.get(..10).unwrap_or(taken)
+}
+
+fn sql(err: rusqlite::Error) -> Error {
+    Error::History(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sprints_are_listed_with_their_scope_changes() {
+        let mut history = History::with_connection(Connection::open_in_memory().unwrap()).unwrap();
+        let sprint = |committed, completed| SprintMetrics {
+            sprint_id: 10,
+            name: "Sprint 10".to_owned(),
+            committed,
+            completed,
+        };
+        let taken = |taken: &str| Snapshot {
+            taken: taken.to_owned(),
+            ..Default::default()
+        };
+        history
+            .record(
+                "Sprint 10",
+                &taken("2024-03-04T09:00:00+00:00"),
+                &[sprint(57600, 0)],
+            )
+            .unwrap();
+        history
+            .record(
+                "Sprint 10",
+                &taken("2024-03-08T09:00:00+00:00"),
+                &[sprint(86400, 28800)],
+            )
+            .unwrap();
+
+        let (_, table) = history.velocity_table(&Durations::default()).unwrap();
+
+        let row = table.get_row(0).unwrap();
+        let content = |cell: usize| row.get_cell(cell).unwrap().get_content();
+        assert_eq!(table.len(), 1);
+        assert_eq!(content(1), "2");
+        assert_eq!(content(2), "2024-03-08");
+        assert_eq!(content(3), "3.0d");
+        assert_eq!(content(4), "+1.0d");
+        assert_eq!(content(5), "1.0d");
+    }
+}
@@ -0,0 +1,70 @@
+use log::{info, warn};
+use serde_json::Value;
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Runs the hooks of a command, each with the output of the command as JSON
+/// on stdin. Like usage stats, a failing hook never fails the command.
+pub fn run(command: &str, hooks: &[String], output: &Value) {
+    let payload = output.to_string();
+    for hook in hooks {
+        info!("running hook for `{}`: {}", command, hook);
+        if let Err(err) = run_hook(command, hook, &payload) {
+            warn!("hook `{}` failed: {}", hook, err);
+        }
+    }
+}
+
+fn run_hook(command: &str, hook: &str, payload: &str) -> std::io::Result<()> {
+    let mut shell = match cfg!(windows) {
+        true => {
+            let mut shell = Command::new("cmd");
+            shell.arg("/C");
+            shell
+        }
+        false => {
+            let mut shell = Command::new("sh");
+            shell.arg("-c");
+            shell
+        }
+    };
+    let mut child = shell
+        .arg(hook)
+        .env("JIRA_COMMAND", command)
+        .stdin(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(payload.as_bytes())?;
+    }
+
+    let status = child.wait()?;
+    match status.success() {
+        true => Ok(()),
+        false => Err(std::io::Error::other(format!("exited with {}", status))),
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn hooks_get_the_output_on_stdin() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("output.json");
+        let hooks = vec![
+            "exit 1".to_owned(),
+            format!("cat > {}", path.display()),
+            format!("echo \" $JIRA_COMMAND\" >> {}", path.display()),
+        ];
+
+        run("sprints health", &hooks, &json!({ "tables": [] }));
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "{\"tables\":[]} sprints health\n"
+        );
+    }
+}
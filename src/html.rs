@@ -0,0 +1,168 @@
+use crate::cards::escape;
+
+use prettytable::{Row, Table};
+
+const CHART_WIDTH: f64 = 640.0;
+const CHART_HEIGHT: f64 = 240.0;
+const AXIS: f64 = 40.0;
+
+/// Estimated work committed to and completed in a closed sprint, in days.
+pub struct Velocity {
+    pub sprint: String,
+    pub committed: f64,
+    pub completed: f64,
+}
+
+/// Renders a report as a standalone HTML page, with a velocity chart of the
+/// last closed sprints above the table when there are any.
+pub fn report(
+    title: &str,
+    titles: &Row,
+    table: &Table,
+    velocity: &[Velocity],
+    generated: &str,
+) -> String {
+    let mut output = HTML_HEADER.replace("{title}", &escape(title));
+    output.push_str(&format!("<h1>{}</h1>\n", escape(title)));
+
+    if !velocity.is_empty() {
+        output.push_str("<h2>Velocity</h2>\n");
+        output.push_str(&chart(velocity));
+    }
+
+    output.push_str("<table>\n<tr>");
+    for cell in titles.iter() {
+        output.push_str(&format!("<th>{}</th>", escape(&cell.get_content())));
+    }
+    output.push_str("</tr>\n");
+    for row in table.row_iter() {
+        output.push_str("<tr>");
+        for cell in row.iter() {
+            let content = escape(&cell.get_content()).replace('\n', "<br>");
+            output.push_str(&format!("<td>{}</td>", content));
+        }
+        output.push_str("</tr>\n");
+    }
+    output.push_str("</table>\n");
+
+    output.push_str(&format!(
+        "<p class=\"generated\">Generated at {}</p>\n",
+        escape(generated)
+    ));
+    output.push_str(HTML_FOOTER);
+    output
+}
+
+/// Draws a bar chart with the committed and completed days per sprint.
+fn chart(velocity: &[Velocity]) -> String {
+    let max = velocity
+        .iter()
+        .map(|v| v.committed.max(v.completed))
+        .fold(1.0, f64::max);
+    let (width, height) = (CHART_WIDTH - AXIS, CHART_HEIGHT - AXIS);
+    let group = width / velocity.len() as f64;
+    let bar = (group / 3.0).min(40.0);
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n",
+        CHART_WIDTH, CHART_HEIGHT, CHART_WIDTH, CHART_HEIGHT
+    );
+    svg.push_str(&format!(
+        "<line x1=\"{0}\" y1=\"0\" x2=\"{0}\" y2=\"{1}\" stroke=\"#000\"/>\n\
+         <line x1=\"{0}\" y1=\"{1}\" x2=\"{2}\" y2=\"{1}\" stroke=\"#000\"/>\n\
+         <text x=\"{3}\" y=\"12\" text-anchor=\"end\">{4:.0}d</text>\n",
+        AXIS,
+        height,
+        CHART_WIDTH,
+        AXIS - 4.0,
+        max
+    ));
+
+    for (i, sprint) in velocity.iter().enumerate() {
+        let x = AXIS + group * i as f64 + (group - bar * 2.0) / 2.0;
+        for (offset, days, class) in &[
+            (0.0, sprint.committed, "committed"),
+            (bar, sprint.completed, "completed"),
+        ] {
+            let h = days / max * (height - 16.0);
+            svg.push_str(&format!(
+                "<rect class=\"{}\" x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\"><title>{:.1}d</title></rect>\n",
+                class,
+                x + offset,
+                height - h,
+                bar,
+                h,
+                days
+            ));
+        }
+        svg.push_str(&format!(
+            "<text x=\"{:.1}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n",
+            x + bar,
+            height + 16.0,
+            escape(&sprint.sprint)
+        ));
+    }
+
+    svg.push_str("</svg>\n<p class=\"legend\"><span class=\"committed\"></span> Committed <span class=\"completed\"></span> Completed</p>\n");
+    svg
+}
+
+const HTML_HEADER: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+  body { font-family: Helvetica, Arial, sans-serif; margin: 2em; color: #172b4d; }
+  table { border-collapse: collapse; margin-top: 1em; }
+  th, td { border-bottom: 1px solid #dfe1e6; padding: 0.4em 1em; text-align: left; vertical-align: top; }
+  svg text { font-size: 11px; }
+  .committed { fill: #c1c7d0; background: #c1c7d0; }
+  .completed { fill: #36b37e; background: #36b37e; }
+  .legend span { display: inline-block; width: 1em; height: 1em; vertical-align: middle; }
+  .generated { color: #6b778c; font-size: 0.9em; }
+</style>
+</head>
+<body>
+"#;
+
+const HTML_FOOTER: &str = "</body>\n</html>\n";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use prettytable::row;
+
+    #[test]
+    fn reports_include_the_table_and_a_bar_per_sprint() {
+        let mut table = Table::new();
+        table.add_row(row!["Jane <Doe>", "2", "1.5d"]);
+        let velocity = vec![
+            Velocity {
+                sprint: "Sprint 8".to_owned(),
+                committed: 10.0,
+                completed: 8.0,
+            },
+            Velocity {
+                sprint: "Sprint 9".to_owned(),
+                committed: 12.0,
+                completed: 12.0,
+            },
+        ];
+
+        let output = report(
+            "Sprint report",
+            &row!["Assignee", "Issues", "Estimated"],
+            &table,
+            &velocity,
+            "2020-06-01 09:00",
+        );
+
+        assert!(output.contains("<title>Sprint report</title>"));
+        assert!(output.contains("<td>Jane &lt;Doe&gt;</td><td>2</td><td>1.5d</td>"));
+        assert_eq!(output.matches("<rect class=\"completed\"").count(), 2);
+        assert!(output.contains(">Sprint 9</text>"));
+        assert!(output.contains("Generated at 2020-06-01 09:00"));
+    }
+}
@@ -0,0 +1,153 @@
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
+use goji::Sprint;
+
+/// Renders sprints as an iCalendar file with an event spanning each sprint,
+/// and optionally all-day review and retro placeholders on the last day of a
+/// sprint. Sprints without a start or end date are skipped.
+pub fn calendar(
+    sprints: &[Sprint],
+    ceremonies: bool,
+    domain: &str,
+    tz: &Tz,
+    now: DateTime<Utc>,
+) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_owned(),
+        "VERSION:2.0".to_owned(),
+        "PRODID:-//svanharmelen//Jira Sprint Helper//EN".to_owned(),
+        "CALSCALE:GREGORIAN".to_owned(),
+    ];
+    let stamp = now.format("%Y%m%dT%H%M%SZ").to_string();
+
+    for sprint in sprints {
+        let (start, end) = match (parse(&sprint.start_date), parse(&sprint.end_date)) {
+            (Some(start), Some(end)) => (start, end),
+            _ => continue,
+        };
+
+        lines.extend(event(
+            &format!("sprint-{}@{}", sprint.id, domain),
+            &stamp,
+            &sprint.name,
+            format!("DTSTART:{}", start.format("%Y%m%dT%H%M%SZ")),
+            format!("DTEND:{}", end.format("%Y%m%dT%H%M%SZ")),
+        ));
+
+        if ceremonies {
+            let day = end.with_timezone(tz).naive_local().date();
+            for (kind, title) in &[("review", "Sprint review"), ("retro", "Retrospective")] {
+                lines.extend(event(
+                    &format!("{}-{}@{}", kind, sprint.id, domain),
+                    &stamp,
+                    &format!("{}: {}", title, sprint.name),
+                    format!("DTSTART;VALUE=DATE:{}", day.format("%Y%m%d")),
+                    format!(
+                        "DTEND;VALUE=DATE:{}",
+                        (day + Duration::days(1)).format("%Y%m%d")
+                    ),
+                ));
+            }
+        }
+    }
+
+    lines.push("END:VCALENDAR".to_owned());
+    lines.join("\r\n") + "\r\n"
+}
+
+fn event(uid: &str, stamp: &str, summary: &str, start: String, end: String) -> Vec<String> {
+    vec![
+        "BEGIN:VEVENT".to_owned(),
+        format!("UID:{}", uid),
+        format!("DTSTAMP:{}", stamp),
+        format!("SUMMARY:{}", escape(summary)),
+        start,
+        end,
+        "END:VEVENT".to_owned(),
+    ]
+}
+
+fn parse(date: &Option<String>) -> Option<DateTime<Utc>> {
+    date.as_ref()
+        .and_then(|v| DateTime::parse_from_rfc3339(v).ok())
+        .map(|v| v.with_timezone(&Utc))
+}
+
+fn escape(input: &str) -> String {
+    input
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    fn sprint(id: u64, name: &str, start: Option<&str>, end: Option<&str>) -> Sprint {
+        serde_json::from_value(json!({
+            "id": id,
+            "self": "",
+            "name": name,
+            "state": "active",
+            "startDate": start,
+            "endDate": end,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn sprints_are_rendered_as_events() {
+        let sprints = vec![
+            sprint(
+                10,
+                "Sprint 10, the one with the migration",
+                Some("2020-06-01T07:00:00.000Z"),
+                Some("2020-06-14T22:30:00.000Z"),
+            ),
+            sprint(11, "Sprint 11", None, None),
+        ];
+        let now = "2020-05-20T12:00:00Z".parse().unwrap();
+
+        let calendar = calendar(
+            &sprints,
+            true,
+            "example.atlassian.net",
+            &chrono_tz::Europe::Amsterdam,
+            now,
+        );
+
+        assert_eq!(
+            calendar,
+            "BEGIN:VCALENDAR\r\n\
+             VERSION:2.0\r\n\
+             PRODID:-//svanharmelen//Jira Sprint Helper//EN\r\n\
+             CALSCALE:GREGORIAN\r\n\
+             BEGIN:VEVENT\r\n\
+             UID:sprint-10@example.atlassian.net\r\n\
+             DTSTAMP:20200520T120000Z\r\n\
+             SUMMARY:Sprint 10\\, the one with the migration\r\n\
+             DTSTART:20200601T070000Z\r\n\
+             DTEND:20200614T223000Z\r\n\
+             END:VEVENT\r\n\
+             BEGIN:VEVENT\r\n\
+             UID:review-10@example.atlassian.net\r\n\
+             DTSTAMP:20200520T120000Z\r\n\
+             SUMMARY:Sprint review: Sprint 10\\, the one with the migration\r\n\
+             DTSTART;VALUE=DATE:20200615\r\n\
+             DTEND;VALUE=DATE:20200616\r\n\
+             END:VEVENT\r\n\
+             BEGIN:VEVENT\r\n\
+             UID:retro-10@example.atlassian.net\r\n\
+             DTSTAMP:20200520T120000Z\r\n\
+             SUMMARY:Retrospective: Sprint 10\\, the one with the migration\r\n\
+             DTSTART;VALUE=DATE:20200615\r\n\
+             DTEND;VALUE=DATE:20200616\r\n\
+             END:VEVENT\r\n\
+             END:VCALENDAR\r\n"
+        );
+    }
+}
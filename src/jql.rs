@@ -0,0 +1,169 @@
+/// Renders a value for JQL: IDs and issue keys as is, anything else as a
+/// quoted string, so names with spaces, quotes or reserved words like `AND`
+/// don't break the query. Fields matched by name use `eq_name` instead.
+pub fn value(value: &str) -> String {
+    match is_id(value) || is_issue_key(value) {
+        true => value.to_owned(),
+        false => quote(value),
+    }
+}
+
+/// Quotes a value as a JQL string.
+pub fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' | '\\' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Returns a `field=value` clause.
+pub fn eq(field: &str, value: &str) -> String {
+    format!("{}={}", field, self::value(value))
+}
+
+/// Returns a `field="name"` clause for fields that Jira matches by ID when the
+/// value is a number, like `fixVersion` or `component`, so a version named
+/// `2024` is still matched by name.
+pub fn eq_name(field: &str, name: &str) -> String {
+    format!("{}={}", field, quote(name))
+}
+
+/// Returns a clause comparing a field with an operator like `<=` or `!=`.
+pub fn cmp(field: &str, operator: &str, value: &str) -> String {
+    format!("{} {} {}", field, operator, self::value(value))
+}
+
+/// Returns a `field in (..)` clause.
+pub fn is_in<'a, I: IntoIterator<Item = &'a str>>(field: &str, values: I) -> String {
+    format!("{} in ({})", field, list(values))
+}
+
+/// Returns a `field in (..)` clause with the values matched by name, like
+/// `eq_name`.
+pub fn in_names<'a, I: IntoIterator<Item = &'a str>>(field: &str, names: I) -> String {
+    let names: Vec<String> = names.into_iter().map(quote).collect();
+    format!("{} in ({})", field, names.join(", "))
+}
+
+/// Returns a `field not in (..)` clause.
+pub fn not_in<'a, I: IntoIterator<Item = &'a str>>(field: &str, values: I) -> String {
+    format!("{} not in ({})", field, list(values))
+}
+
+/// Returns a `field ~ "text"` clause that searches for the text as is. Jira's
+/// text search treats characters like `+`, `-` and `*` as operators, so these
+/// are escaped twice: once for the search and once for the JQL string.
+/// Double quotes are kept, to search for an exact phrase.
+pub fn contains(field: &str, text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len() + 2);
+    escaped.push('"');
+    for c in text.trim().chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\\\\\"),
+            '+' | '-' | '&' | '|' | '!' | '(' | ')' | '{' | '}' | '[' | ']' | '^' | '~' | '*'
+            | '?' | ':' | '/' => {
+                escaped.push_str("\\\\");
+                escaped.push(c);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    format!("{} ~ {}", field, escaped)
+}
+
+fn list<'a, I: IntoIterator<Item = &'a str>>(values: I) -> String {
+    values
+        .into_iter()
+        .map(value)
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+fn is_id(value: &str) -> bool {
+    !value.is_empty() && value.chars().all(|c| c.is_ascii_digit())
+}
+
+fn is_issue_key(value: &str) -> bool {
+    match value.split_once('-') {
+        Some((project, number)) => {
+            project.starts_with(|c: char| c.is_ascii_uppercase())
+                && project
+                    .chars()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+                && is_id(number)
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_are_quoted_unless_they_are_ids_or_issue_keys() {
+        assert_eq!(value("10"), "10");
+        assert_eq!(value("ABC-12"), "ABC-12");
+        assert_eq!(value("AB_2-1"), "AB_2-1");
+        assert_eq!(value("abc-12"), "\"abc-12\"");
+        assert_eq!(value("ABC-"), "\"ABC-\"");
+        assert_eq!(value("AND"), "\"AND\"");
+        assert_eq!(value(""), "\"\"");
+        assert_eq!(value("Jane O'Brien"), "\"Jane O'Brien\"");
+        assert_eq!(value("The \"A\" team"), r#""The \"A\" team""#);
+        assert_eq!(value(r"C:\temp"), r#""C:\\temp""#);
+        assert_eq!(value("two\nlines"), r#""two\nlines""#);
+    }
+
+    #[test]
+    fn clauses_are_built_with_escaped_values() {
+        assert_eq!(eq("sprint", "10"), "sprint=10");
+        assert_eq!(
+            eq("fixVersion", "2.0 \"beta\""),
+            r#"fixVersion="2.0 \"beta\"""#
+        );
+        assert_eq!(cmp("duedate", "<=", "3d"), "duedate <= \"3d\"");
+        assert_eq!(
+            is_in("labels", vec!["tech-debt", "needs review"]),
+            "labels in (\"tech-debt\", \"needs review\")"
+        );
+        assert_eq!(not_in("status", vec!["5", "6"]), "status not in (5, 6)");
+        assert_eq!(is_in("parent", vec!["ABC-1"]), "parent in (ABC-1)");
+    }
+
+    #[test]
+    fn names_are_always_quoted() {
+        assert_eq!(eq_name("fixVersion", "2024"), "fixVersion=\"2024\"");
+        assert_eq!(eq_name("project", "ABC"), "project=\"ABC\"");
+        assert_eq!(
+            in_names("component", vec!["10", "API-2"]),
+            "component in (\"10\", \"API-2\")"
+        );
+    }
+
+    #[test]
+    fn text_is_searched_as_is() {
+        assert_eq!(
+            contains("text", " payment gateway "),
+            "text ~ \"payment gateway\""
+        );
+        assert_eq!(
+            contains("summary", "\"retry\" (C++) a\\b"),
+            r#"summary ~ "\"retry\" \\(C\\+\\+\\) a\\\\b""#
+        );
+        assert_eq!(contains("text", "50%*"), r#"text ~ "50%\\*""#);
+    }
+}
@@ -0,0 +1,193 @@
+use prettytable::{Cell, Row, Table};
+
+/// Columns are not shrunk below this width, so keys, statuses and names stay
+/// readable.
+const MIN_WIDTH: usize = 12;
+
+/// Shrinks the columns that are wider than needed until the table fits in
+/// `width` characters, taking more from wider columns (like summaries) than
+/// from narrow ones. Lines that no longer fit are cut off with `...`, or
+/// wrapped onto multiple lines with `wrap`.
+pub fn fit(titles: &Row, table: &mut Table, width: usize, wrap: bool) {
+    let widths = widths(titles, table, width);
+    for row in table.row_iter_mut() {
+        if wrap {
+            wrap_row(row, &widths);
+            continue;
+        }
+        for (cell, width) in row.iter_mut().zip(widths.iter()) {
+            let content = cell.get_content();
+            if content.lines().any(|v| v.chars().count() > *width) {
+                let lines: Vec<String> = content.lines().map(|v| truncate(v, *width)).collect();
+                *cell = Cell::new(&lines.join("\n"));
+            }
+        }
+    }
+}
+
+/// Returns the width each column gets to fit the table in `width`
+/// characters, starting from the width of the longest line in the column.
+fn widths(titles: &Row, table: &Table, width: usize) -> Vec<usize> {
+    let mut widths: Vec<usize> = titles.iter().map(content_width).collect();
+    for row in table.row_iter() {
+        for (i, cell) in row.iter().enumerate() {
+            match widths.get_mut(i) {
+                Some(width) => *width = (*width).max(content_width(cell)),
+                None => widths.push(content_width(cell)),
+            }
+        }
+    }
+
+    // Each column is padded by a space on both sides and followed by a border.
+    let total = widths.iter().sum::<usize>() + widths.len() * 3 + 1;
+    let shrinkable: usize = widths.iter().map(|v| v.saturating_sub(MIN_WIDTH)).sum();
+    if total <= width || shrinkable == 0 {
+        return widths;
+    }
+
+    let excess = (total - width).min(shrinkable);
+    for width in widths.iter_mut() {
+        let spare = width.saturating_sub(MIN_WIDTH);
+        *width -= (spare * excess).div_ceil(shrinkable).min(spare);
+    }
+    widths
+}
+
+/// Wraps the lines of the cells in a row, adding empty lines to the other
+/// cells so lines that belong together (like a sub-task and its status)
+/// stay next to each other.
+fn wrap_row(row: &mut Row, widths: &[usize]) {
+    let cells: Vec<String> = row.iter().map(|v| v.get_content()).collect();
+    let too_long = cells
+        .iter()
+        .zip(widths.iter())
+        .any(|(content, width)| content.lines().any(|v| v.chars().count() > *width));
+    if !too_long {
+        return;
+    }
+
+    let height = cells.iter().map(|v| v.lines().count()).max().unwrap_or(0);
+    let mut wrapped: Vec<Vec<String>> = vec![Vec::new(); cells.len()];
+    for i in 0..height {
+        let mut pieces: Vec<Vec<String>> = cells
+            .iter()
+            .zip(widths.iter())
+            .map(|(content, width)| match content.lines().nth(i) {
+                Some(line) => wrap(line, *width),
+                None => Vec::new(),
+            })
+            .collect();
+        let lines = pieces.iter().map(|v| v.len()).max().unwrap_or(0);
+        for (cell, pieces) in wrapped.iter_mut().zip(pieces.iter_mut()) {
+            pieces.resize(lines, String::new());
+            cell.append(pieces);
+        }
+    }
+
+    for (cell, lines) in row.iter_mut().zip(wrapped.iter()) {
+        *cell = Cell::new(lines.join("\n").trim_end_matches('\n'));
+    }
+}
+
+/// Wraps a line at spaces, splitting words that are longer than a line.
+fn wrap(line: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in line.split(' ') {
+        let mut word: Vec<char> = word.chars().collect();
+        while word.len() > width {
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+            }
+            lines.push(word.drain(..width).collect());
+        }
+
+        let length = current.chars().count();
+        if length > 0 && length + 1 + word.len() > width {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.extend(word);
+    }
+    lines.push(current);
+    lines
+}
+
+fn content_width(cell: &Cell) -> usize {
+    cell.get_content()
+        .lines()
+        .map(|v| v.chars().count())
+        .max()
+        .unwrap_or(0)
+}
+
+fn truncate(line: &str, width: usize) -> String {
+    match line.chars().count() > width {
+        true => {
+            let mut line: String = line.chars().take(width.saturating_sub(3)).collect();
+            line.push_str("...");
+            line
+        }
+        false => line.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prettytable::row;
+
+    #[test]
+    fn wide_columns_are_shrunk_to_fit() {
+        let titles = row!["Key", "Summary", "Sub-Tasks"];
+        let mut table = Table::new();
+        table.add_row(row![
+            "ABC-1",
+            "s".repeat(60),
+            format!("{}\nshort", "t".repeat(30))
+        ]);
+
+        // 5 + 60 + 30 plus 10 for the borders and padding.
+        assert_eq!(widths(&titles, &table, 105), [5, 60, 30]);
+        assert_eq!(widths(&titles, &table, 80), [5, 41, 23]);
+        assert_eq!(widths(&titles, &table, 10), [5, 12, 12]);
+
+        fit(&titles, &mut table, 80, false);
+        let row = table.get_row(0).unwrap();
+        assert_eq!(row.get_cell(0).unwrap().get_content(), "ABC-1");
+        assert_eq!(
+            row.get_cell(1).unwrap().get_content(),
+            format!("{}...", "s".repeat(38))
+        );
+        assert_eq!(
+            row.get_cell(2).unwrap().get_content(),
+            format!("{}...\nshort", "t".repeat(20))
+        );
+    }
+
+    #[test]
+    fn lines_are_wrapped_next_to_the_lines_they_belong_to() {
+        let titles = row!["Key", "Sub-Tasks", "Status"];
+        let mut table = Table::new();
+        table.add_row(row![
+            "ABC-1",
+            "ABC-2: Sum the remaining estimates – part 2 of 3\nABC-3: Docs",
+            "To Do\nDone"
+        ]);
+
+        fit(&titles, &mut table, 40, true);
+
+        let row = table.get_row(0).unwrap();
+        assert_eq!(row.get_cell(0).unwrap().get_content(), "ABC-1");
+        assert_eq!(
+            row.get_cell(1).unwrap().get_content(),
+            "ABC-2: Sum the\nremaining estimates\n– part 2 of 3\nABC-3: Docs"
+        );
+        assert_eq!(row.get_cell(2).unwrap().get_content(), "To Do\n\n\nDone");
+
+        assert_eq!(wrap("abcdefghij klm", 4), ["abcd", "efgh", "ij", "klm"]);
+        assert_eq!(wrap("", 4), [""]);
+    }
+}
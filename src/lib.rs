@@ -1,12 +1,105 @@
+//! Sprint reports, issue queries and estimate rollups for Jira, as used by
+//! the `jira` binary.
+//!
+//! To use them from another tool, create a [`Client`] with
+//! [`Client::builder`] (or for any [`JiraApi`] with [`Client::from_api`])
+//! and call the methods that return data:
+//!
+//! ```no_run
+//! use jira::Client;
+//!
+//! # fn main() -> jira::Result<()> {
+//! let client = Client::builder()
+//!     .host("https://example.atlassian.net")
+//!     .credentials("jane@example.com", "token")
+//!     .build()?;
+//!
+//! for (assignee, totals) in client.sprint_totals("10")? {
+//!     println!("{}: {}s remaining", assignee, totals.remaining);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The commands of the binary send what they produce to the [`Output`] given
+//! to [`ClientBuilder::output`], like a [`Recorder`] that keeps it.
+
 #[macro_use]
 mod macros;
 
+pub mod api;
+pub use api::{Account, JiraApi};
+
+pub mod attachments;
+
+pub mod browser;
+
+pub mod bulk;
+
+pub mod cache;
+pub use cache::Cache;
+
+pub mod cancel;
+
+pub mod cards;
+
 pub mod client;
-pub use client::Client;
+
+pub mod clipboard;
+pub use client::{Client, ClientBuilder};
+
+pub mod config;
+pub use config::Config;
+
+pub mod confluence;
+
+pub mod diff;
+
+pub mod duration;
 
 pub mod error;
+
+#[cfg(feature = "history")]
+pub mod history;
+
+pub mod hooks;
 pub use error::Error;
 
+pub mod html;
+
+pub mod http;
+
+pub mod ics;
+
+pub mod jql;
+
+pub mod layout;
+
+pub mod markdown;
+
+pub mod metrics;
+
+pub mod options;
+
+pub mod output;
+pub use output::{Output, Recorder};
+
+pub mod shell;
+
+pub mod snapshot;
+
+pub mod stats;
+
+pub mod teams;
+
+#[cfg(feature = "tempo")]
+pub mod tempo;
+
+pub mod timer;
+pub use timer::Timer;
+
+pub mod tracker;
+
 pub mod users;
 pub use users::*;
 
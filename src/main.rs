@@ -1,189 +1,135 @@
-use jira::Client;
+mod cli;
+
+use cli::app::app;
+use cli::commands::{self, dispatch};
+use cli::terminal::Terminal;
+use cli::{completions, leaf, repl};
+use jira::stats::Stats;
+use jira::{cancel, Config, Error};
 
 use anyhow::Result;
-use clap::{crate_authors, crate_version, App, AppSettings, Arg, ArgGroup};
+use clap::{ArgMatches, ErrorKind};
+use log::warn;
+
+use std::env;
+use std::process;
+use std::rc::Rc;
 
 fn main() -> Result<()> {
-    let global_args = vec![
-        Arg::with_name("organization")
-            .help("Organization")
-            .short("o")
-            .long("organization")
-            .env("JIRA_ORGANIZATION")
-            .empty_values(false)
-            .hide_env_values(true)
-            .display_order(1)
-            .required(true),
-        Arg::with_name("user")
-            .help("User")
-            .short("u")
-            .long("user")
-            .env("JIRA_USER")
-            .empty_values(false)
-            .hide_env_values(true)
-            .display_order(2)
-            .required(true),
-        Arg::with_name("token")
-            .help("Token")
-            .short("t")
-            .long("token")
-            .env("JIRA_TOKEN")
-            .empty_values(false)
-            .hide_env_values(true)
-            .display_order(3)
-            .required(true),
-    ];
+    // Aliases from the config never shadow commands, so they are only tried
+    // when the arguments don't parse as a command.
+    let args: Vec<String> = env::args().collect();
+    let matches = match app().get_matches_from_safe(&args) {
+        Ok(matches) => matches,
+        Err(err)
+            if err.kind == ErrorKind::HelpDisplayed || err.kind == ErrorKind::VersionDisplayed =>
+        {
+            err.exit()
+        }
+        // A broken config file shouldn't hide the usage error.
+        Err(err) => match Config::load().ok().and_then(|v| v.expand_alias(&args)) {
+            Some(args) => app().get_matches_from(args),
+            None => err.exit(),
+        },
+    };
 
-    let app = App::new("Jira Sprint Helper")
-        .about("A small tool to help prepare, start and complete sprints in Jira")
-        .author(crate_authors!())
-        .version(crate_version!())
-        .setting(AppSettings::SubcommandRequiredElseHelp)
-        .global_setting(AppSettings::ColorNever)
-        .subcommand(
-            App::new("boards")
-                .about("List all boards you have access to")
-                .args(&global_args)
-                .display_order(1),
-        )
-        .subcommand(
-            App::new("sprints")
-                .about("List and filter sprints from a given board")
-                .args(&global_args)
-                .args(&[
-                    Arg::with_name("board")
-                        .help("Board ID from which to fetch sprints")
-                        .short("b")
-                        .long("board-id")
-                        .required(true)
-                        .takes_value(true)
-                        .validator(|v| match v.parse::<u64>() {
-                            Ok(_) => Ok(()),
-                            Err(_) => Err("board ID is not a number".to_owned()),
-                        }),
-                    Arg::with_name("all")
-                        .help("Also show closed sprints")
-                        .short("A")
-                        .long("all")
-                        .group("filter")
-                        .display_order(1),
-                    Arg::with_name("active")
-                        .short("a")
-                        .long("active")
-                        .help("Only show active sprints")
-                        .group("filter")
-                        .display_order(2),
-                    Arg::with_name("future")
-                        .help("Only show future sprints")
-                        .short("f")
-                        .long("future")
-                        .group("filter")
-                        .display_order(3),
-                ])
-                .display_order(2),
-        )
-        .subcommand(
-            App::new("issues")
-                .about("List, filter and search issues from a given board")
-                .args(&global_args)
-                .args(&[
-                    Arg::with_name("board")
-                        .help("Board ID from which to fetch issues")
-                        .short("b")
-                        .long("board-id")
-                        .group("select")
-                        .takes_value(true)
-                        .display_order(4)
-                        .validator(|v| match v.parse::<u64>() {
-                            Ok(_) => Ok(()),
-                            Err(_) => Err("board ID is not a number".to_owned()),
-                        }),
-                    Arg::with_name("sprint")
-                        .help("Sprint ID from which to fetch issues")
-                        .short("s")
-                        .long("sprint-id")
-                        .group("select")
-                        .takes_value(true)
-                        .display_order(5)
-                        .validator(|v| match v.parse::<u64>() {
-                            Ok(_) => Ok(()),
-                            Err(_) => Err("sprint ID is not a number".to_owned()),
-                        }),
-                    Arg::with_name("assignee")
-                        .help("Only show issues for a given assignee")
-                        .short("a")
-                        .long("assignee")
-                        .group("filter")
-                        .takes_value(true)
-                        .display_order(6),
-                    Arg::with_name("issue")
-                        .help("Show details from a specific issue")
-                        .short("i")
-                        .long("issue")
-                        .group("filter")
-                        .takes_value(true)
-                        .display_order(7),
-                    Arg::with_name("all")
-                        .help("Also show issues that are done")
-                        .short("A")
-                        .long("all")
-                        .display_order(1),
-                    Arg::with_name("no-subtasks")
-                        .help("Only show stories, tasks and bugs")
-                        .short("S")
-                        .long("no-subtasks")
-                        .display_order(2),
-                ])
-                .group(ArgGroup::with_name("select").required(true))
-                .display_order(3),
-        )
-        .subcommand(
-            App::new("report")
-                .about("Show and update original estimates and time logged")
-                .args(&global_args)
-                .args(&[
-                    Arg::with_name("board")
-                        .help("Board ID from which to fetch issues")
-                        .short("b")
-                        .long("board-id")
-                        .group("select")
-                        .takes_value(true)
-                        .display_order(4)
-                        .validator(|v| match v.parse::<u64>() {
-                            Ok(_) => Ok(()),
-                            Err(_) => Err("board ID is not a number".to_owned()),
-                        }),
-                    Arg::with_name("sprint")
-                        .help("Sprint ID from which to fetch issues")
-                        .short("s")
-                        .long("sprint-id")
-                        .group("select")
-                        .takes_value(true)
-                        .display_order(5)
-                        .validator(|v| match v.parse::<u64>() {
-                            Ok(_) => Ok(()),
-                            Err(_) => Err("sprint ID is not a number".to_owned()),
-                        }),
-                    Arg::with_name("planning")
-                        .help("Ignore issues that are done")
-                        .short("p")
-                        .long("planning")
-                        .display_order(1),
-                    Arg::with_name("reset")
-                        .help("Reset remaining time for subtasks")
-                        .short("r")
-                        .long("reset")
-                        .display_order(2),
-                ])
-                .display_order(4),
-        )
-        .get_matches();
+    let filter = match verbosity(&matches) {
+        0 => "warn",
+        1 => "warn,jira=info",
+        _ => "warn,jira=debug,goji=debug,reqwest=debug",
+    };
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(filter))
+        .format_timestamp_millis()
+        .init();
 
-    match app.subcommand() {
-        ("boards", Some(options)) => Ok(Client::new(options)?.boards()?),
-        ("sprints", Some(options)) => Ok(Client::new(options)?.sprints(options)?),
-        ("issues", Some(options)) => Ok(Client::new(options)?.issues(options)?),
-        ("report", Some(options)) => Ok(Client::new(options)?.report(options)?),
+    match matches.subcommand() {
+        ("completions", Some(options)) => Ok(completions::print(app(), options)?),
+        ("stats", Some(options)) => Ok(commands::stats(&Terminal::new(options), options)?),
+        #[cfg(feature = "history")]
+        ("history", Some(options)) => Ok(commands::history(&Terminal::new(options), options)?),
+        ("repl", Some(options)) => {
+            let terminal = Rc::new(Terminal::new(options));
+            let client = commands::client(options, terminal.clone())?;
+            Ok(repl::run(app, options, |matches| {
+                let result = dispatch(&client, &terminal, matches);
+                if result.is_ok() {
+                    client.run_hooks(&command(matches));
+                }
+                record(matches, result.is_ok());
+                result
+            })?)
+        }
+        #[cfg(feature = "serve")]
+        ("serve", Some(options)) => {
+            let client = commands::client(options, Rc::new(Terminal::new(options)))?;
+            Ok(cli::serve::run(app, &client, options)?)
+        }
+        (_, Some(options)) => {
+            cancel::install();
+            let terminal = Rc::new(Terminal::new(leaf(options)));
+            let result = commands::client(leaf(options), terminal.clone()).and_then(|client| {
+                let result = dispatch(&client, &terminal, &matches);
+                if let Some((titles, mut table)) = client.timings() {
+                    // Kept apart from the output, so it can still be piped.
+                    table.set_titles(titles);
+                    eprint!("\n{}\n", table);
+                }
+                if result.is_ok() {
+                    client.run_hooks(&command(&matches));
+                }
+                result
+            });
+            record(&matches, result.is_ok());
+            // A cancelled command exits with the code a shell uses for Ctrl-C,
+            // and a failed check with a code scripts can tell apart from errors.
+            let code = match result {
+                Err(Error::Cancelled(_)) => 130,
+                Err(Error::Check(_)) => 2,
+                _ => 1,
+            };
+            match result {
+                // Scripts asking for JSON get errors they can handle as well.
+                Err(err) if leaf(options).value_of("output") == Some("json") => {
+                    eprintln!("{}", err.json());
+                    process::exit(code);
+                }
+                Err(err @ Error::Cancelled(_)) | Err(err @ Error::Check(_)) => {
+                    eprintln!("Error: {}", err);
+                    process::exit(code);
+                }
+                result => Ok(result?),
+            }
+        }
         _ => unreachable!(),
     }
 }
+
+/// Records that a command was run when usage stats are enabled. Failing to
+/// do so never fails the command itself.
+fn record(matches: &ArgMatches, ok: bool) {
+    if !Config::load().map(|v| v.stats).unwrap_or(false) {
+        return;
+    }
+
+    if let Err(err) = Stats::record(&command(matches), ok) {
+        warn!("unable to record usage stats: {}", err);
+    }
+}
+
+/// Returns the command and its subcommands, e.g. `report matrix`.
+fn command(matches: &ArgMatches) -> String {
+    let mut command = Vec::new();
+    let mut matches = matches;
+    while let (name, Some(options)) = matches.subcommand() {
+        command.push(name);
+        matches = options;
+    }
+    command.join(" ")
+}
+
+fn verbosity(matches: &ArgMatches) -> u64 {
+    match matches.subcommand() {
+        (_, Some(options)) => matches.occurrences_of("verbose").max(verbosity(options)),
+        _ => matches.occurrences_of("verbose"),
+    }
+}
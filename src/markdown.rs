@@ -0,0 +1,54 @@
+use prettytable::{Row, Table};
+
+/// Renders a table as a GitHub flavored Markdown document with a heading
+/// and a line telling when it was generated.
+pub fn document(title: &str, titles: &Row, table: &Table, generated: &str) -> String {
+    let mut output = format!("## {}\n\n", escape(title));
+
+    output.push_str(&row(titles));
+    output.push_str(&format!("|{}\n", " --- |".repeat(titles.len())));
+    for cells in table.row_iter() {
+        output.push_str(&row(cells));
+    }
+
+    output.push_str(&format!("\n_Generated at {}_\n", generated));
+    output
+}
+
+fn row(row: &Row) -> String {
+    let cells: Vec<String> = row.iter().map(|v| escape(&v.get_content())).collect();
+    format!("| {} |\n", cells.join(" | "))
+}
+
+fn escape(input: &str) -> String {
+    input.replace('|', "\\|").replace('\n', "<br>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use prettytable::row;
+
+    #[test]
+    fn tables_are_rendered_with_a_title_and_timestamp() {
+        let mut table = Table::new();
+        table.add_row(row!["Jane Doe", "2", "1.5d"]);
+        table.add_row(row!["John | Smith", "1", "0.5d"]);
+
+        assert_eq!(
+            document(
+                "Sprint report",
+                &row!["Assignee", "Issues", "Estimated"],
+                &table,
+                "2020-06-01 09:00"
+            ),
+            "## Sprint report\n\n\
+             | Assignee | Issues | Estimated |\n\
+             | --- | --- | --- |\n\
+             | Jane Doe | 2 | 1.5d |\n\
+             | John \\| Smith | 1 | 0.5d |\n\
+             \n_Generated at 2020-06-01 09:00_\n"
+        );
+    }
+}
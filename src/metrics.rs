@@ -0,0 +1,79 @@
+use std::fmt::Write;
+
+/// A gauge in the Prometheus text format, with a sample per set of labels.
+pub struct Gauge {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub samples: Vec<(Vec<(&'static str, String)>, f64)>,
+}
+
+impl Gauge {
+    pub fn new(name: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            help,
+            samples: Vec::new(),
+        }
+    }
+
+    pub fn add(&mut self, labels: Vec<(&'static str, String)>, value: f64) {
+        self.samples.push((labels, value));
+    }
+}
+
+/// Renders gauges in the Prometheus text format (version 0.0.4), which
+/// OpenMetrics scrapers read as well.
+pub fn render(gauges: &[Gauge]) -> String {
+    let mut text = String::new();
+    for gauge in gauges.iter().filter(|v| !v.samples.is_empty()) {
+        let _ = writeln!(text, "# HELP {} {}", gauge.name, gauge.help);
+        let _ = writeln!(text, "# TYPE {} gauge", gauge.name);
+        for (labels, value) in gauge.samples.iter() {
+            let labels: Vec<String> = labels
+                .iter()
+                .map(|(name, value)| format!("{}=\"{}\"", name, escape(value)))
+                .collect();
+            let _ = writeln!(text, "{}{{{}}} {}", gauge.name, labels.join(","), value);
+        }
+    }
+    text
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gauges_are_rendered_with_escaped_labels() {
+        let mut gauge = Gauge::new("jira_sprint_issues", "Issues in the sprint per status.");
+        gauge.add(
+            vec![
+                ("sprint", "Sprint \"10\"".to_owned()),
+                ("status", "To Do".to_owned()),
+            ],
+            3.0,
+        );
+        gauge.add(
+            vec![
+                ("sprint", "Sprint \"10\"".to_owned()),
+                ("status", "Done".to_owned()),
+            ],
+            0.5,
+        );
+
+        assert_eq!(
+            render(&[gauge, Gauge::new("jira_unused", "Not rendered.")]),
+            "# HELP jira_sprint_issues Issues in the sprint per status.\n\
+             # TYPE jira_sprint_issues gauge\n\
+             jira_sprint_issues{sprint=\"Sprint \\\"10\\\"\",status=\"To Do\"} 3\n\
+             jira_sprint_issues{sprint=\"Sprint \\\"10\\\"\",status=\"Done\"} 0.5\n"
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+/// The boards, sprints, saved filter or JQL query a command gets its issues
+/// from. Boards go before sprints, and sprints before a filter or JQL.
+#[derive(Clone, Debug, Default)]
+pub struct Sources {
+    pub boards: Vec<String>,
+    pub sprints: Vec<String>,
+    /// A saved filter by ID, which goes before `filter` and `jql`.
+    pub filter_id: Option<String>,
+    /// A saved filter by name, which goes before `jql`.
+    pub filter: Option<String>,
+    pub jql: Option<String>,
+    /// Uses the active sprints of the boards instead of the boards themselves.
+    pub all_active: bool,
+}
+
+/// What to copy to the clipboard after showing an issue.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Clipboard {
+    Url,
+    Key,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct IssuesOptions {
+    pub sources: Sources,
+    /// Only the issues with work assigned to this user: a name, an email
+    /// address, an account ID, `me` or `unassigned`.
+    pub assignee: Option<String>,
+    /// Only this issue, or the issue of this subtask.
+    pub issue: Option<String>,
+    /// Also lists the done issues.
+    pub all: bool,
+    pub no_subtasks: bool,
+    pub flagged: bool,
+    pub components: Vec<String>,
+    pub fix_version: Option<String>,
+    /// Text to search for, in the summary only with `summary_only`.
+    pub search: Option<String>,
+    pub summary_only: bool,
+    pub priorities: Vec<String>,
+    /// Any of `todo`, `in-progress` and `done`.
+    pub status_categories: Vec<String>,
+    pub overdue: bool,
+    /// A period like `3d` or `2w` the issues are due within.
+    pub due_within: Option<String>,
+    /// Adds a column with the due dates.
+    pub due: bool,
+    pub sort_by_priority: bool,
+    /// Adds a column with the URLs of the issues.
+    pub urls: bool,
+    /// Extra columns, as a field ID, an `ID:Title` or an alias from the config.
+    pub fields: Vec<String>,
+    pub watchers: bool,
+    /// Copies the URL or key of `issue` to the clipboard.
+    pub copy: Option<Clipboard>,
+    /// Any of `no-unestimated`, `no-unassigned` and `capacity`, which fail
+    /// the command when they don't hold.
+    pub checks: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ReportOptions {
+    pub sources: Sources,
+    /// Leaves out the done issues and the time spent.
+    pub planning: bool,
+    /// Resets the remaining estimates of subtasks to their original estimates.
+    pub reset: bool,
+    /// Counts the remaining estimates of done subtasks as zero.
+    pub zero_done_remaining: bool,
+    /// Clears the remaining estimates of done subtasks in Jira.
+    pub clear_done_remaining: bool,
+    /// Logs the time spent on subtasks on their parent issues.
+    pub rollup_worklogs: bool,
+    /// Outputs the updates as a unified diff instead of making them.
+    pub diff: bool,
+    /// Only updates these issues, or the subtasks of them.
+    pub only: Vec<String>,
+    /// Doesn't update these issues, or the subtasks of them.
+    pub exclude: Vec<String>,
+    pub components: Vec<String>,
+    /// Sums the work per component instead of per assignee.
+    pub by_component: bool,
+    /// Splits estimates between the assignee and the co-assignees of subtasks.
+    pub split_co_assignees: bool,
+    /// Adds a row per parent issue under every assignee.
+    pub detail: bool,
+    /// Sums the time logged per author within these dates instead.
+    pub logged: Option<(NaiveDate, NaiveDate)>,
+    /// A snapshot saved by an earlier report to show the changes since.
+    pub compare: Option<String>,
+    /// Where to save a snapshot of the totals.
+    pub save: Option<String>,
+    /// Like the checks of [`IssuesOptions`].
+    pub checks: Vec<String>,
+    /// Posts the report to Microsoft Teams instead.
+    pub teams_webhook: Option<String>,
+    /// Renders the report as a page with a velocity chart.
+    pub html: bool,
+    /// The title of that page.
+    pub title: Option<String>,
+    /// Where to write that page, instead of to the output.
+    pub file: Option<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PublishOptions {
+    pub report: ReportOptions,
+    /// The key of the Confluence space to publish in.
+    pub space: String,
+    /// The title of the page, which defaults to the sources of the report.
+    pub title: Option<String>,
+    /// Defaults to `<host>/wiki`.
+    pub confluence_url: Option<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct SprintsOptions {
+    pub board: String,
+    /// Also lists the closed sprints.
+    pub all: bool,
+    pub active: bool,
+    pub future: bool,
+    /// Only outputs the IDs, one per line.
+    pub quiet: bool,
+    /// Outputs an iCalendar file instead of a table.
+    pub ics: bool,
+    /// Adds the sprint review and retro to that file.
+    pub ceremonies: bool,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct GrepOptions {
+    pub sources: Sources,
+    /// A regular expression.
+    pub pattern: String,
+    pub ignore_case: bool,
+    /// Also searches the done issues.
+    pub all: bool,
+    /// Marks the matches with terminal colors.
+    pub color: bool,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct EstimateOptions {
+    pub issue: String,
+    /// A duration like `2d` or `1d4h`.
+    pub original: Option<String>,
+    pub remaining: Option<String>,
+    pub points: Option<f64>,
+    /// The board whose estimation field holds the points.
+    pub board: Option<String>,
+}
+
+/// Labels or components to add to and remove from issues.
+#[derive(Clone, Debug, Default)]
+pub struct UpdateOptions {
+    pub issues: Vec<String>,
+    pub add: Vec<String>,
+    pub remove: Vec<String>,
+    /// Outputs the changes as a unified diff instead of making them.
+    pub diff: bool,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct CloneOptions {
+    pub issue: String,
+    /// The summary of the copy, instead of the one of the issue.
+    pub summary: Option<String>,
+    /// Also copies the subtasks.
+    pub subtasks: bool,
+    /// The sprint to move the copy to.
+    pub sprint: Option<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct MatrixOptions {
+    pub sources: Sources,
+    /// Counts the issues instead of summing their estimates.
+    pub count: bool,
+    /// Puts the issue types in the rows instead of the assignees.
+    pub by_type: bool,
+}
+
+/// A subtask to create, as given on the command line or in a YAML file.
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct NewSubtask {
+    pub summary: String,
+    pub estimate: Option<String>,
+    pub assignee: Option<String>,
+}
@@ -0,0 +1,89 @@
+use crate::Result;
+
+use prettytable::{Row, Table};
+
+use std::cell::RefCell;
+
+/// Where the commands of a [`Client`](crate::Client) send what they produce,
+/// so the binary decides how it is printed and other tools can keep it.
+pub trait Output {
+    /// A table, or the message to show instead when it has no rows. The
+    /// title is the one of the document when it is rendered as Markdown.
+    fn table(&self, title: &str, titles: Row, table: Table, empty: &str);
+
+    /// Output that is written as is, like a rendered report or a PDF.
+    fn write(&self, bytes: &[u8]);
+
+    /// A line about what a command did, like `Updated ABC-1`.
+    fn message(&self, message: &str);
+
+    /// A line that is kept apart from the output, like an update that failed
+    /// without stopping the other ones.
+    fn note(&self, note: &str);
+
+    /// Asks whether to go ahead with a change that looks like a mistake.
+    fn confirm(&self, _question: &str) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn text(&self, text: &str) {
+        self.write(text.as_bytes());
+    }
+}
+
+/// Keeps everything a command outputs, for tools that embed the client.
+#[derive(Default)]
+pub struct Recorder {
+    pub tables: RefCell<Vec<(Row, Table)>>,
+    /// The messages, notes and text, in the order they were output.
+    pub lines: RefCell<Vec<String>>,
+}
+
+impl Output for Recorder {
+    fn table(&self, _title: &str, titles: Row, table: Table, _empty: &str) {
+        self.tables.borrow_mut().push((titles, table));
+    }
+
+    fn write(&self, bytes: &[u8]) {
+        let text = String::from_utf8_lossy(bytes);
+        self.lines
+            .borrow_mut()
+            .extend(text.lines().map(|v| v.to_owned()));
+    }
+
+    fn message(&self, message: &str) {
+        self.lines.borrow_mut().push(message.to_owned());
+    }
+
+    fn note(&self, note: &str) {
+        self.lines.borrow_mut().push(note.to_owned());
+    }
+}
+
+/// Drops everything, for a client that is only used for its data.
+pub(crate) struct Discard;
+
+impl Output for Discard {
+    fn table(&self, _title: &str, _titles: Row, _table: Table, _empty: &str) {}
+
+    fn write(&self, _bytes: &[u8]) {}
+
+    fn message(&self, _message: &str) {}
+
+    fn note(&self, _note: &str) {}
+}
+
+/// Returns the rows of a table as JSON objects keyed by the titles.
+pub fn json_rows(titles: &Row, table: &Table) -> serde_json::Value {
+    table
+        .row_iter()
+        .map(|row| {
+            titles
+                .iter()
+                .zip(row.iter())
+                .map(|(title, cell)| (title.get_content(), cell.get_content().into()))
+                .collect::<serde_json::Map<String, serde_json::Value>>()
+        })
+        .collect::<Vec<_>>()
+        .into()
+}
@@ -0,0 +1,42 @@
+/// Splits a line into words, keeping quoted parts together.
+pub fn split(line: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quote = None;
+
+    for c in line.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => word.get_or_insert_with(String::new).push(c),
+            (None, '"') | (None, '\'') => {
+                quote = Some(c);
+                word.get_or_insert_with(String::new);
+            }
+            (None, c) if c.is_whitespace() => words.extend(word.take()),
+            (None, c) => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+
+    match quote {
+        Some(_) => None,
+        None => {
+            words.extend(word);
+            Some(words)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quoted_words_are_kept_together() {
+        assert_eq!(
+            split("issues -b 42 --title 'Daily standup' \"\"").unwrap(),
+            ["issues", "-b", "42", "--title", "Daily standup", ""]
+        );
+        assert_eq!(split("  ").unwrap(), Vec::<String>::new());
+        assert_eq!(split("--title 'unterminated"), None);
+    }
+}
@@ -0,0 +1,98 @@
+use crate::duration::Durations;
+use crate::{Result, User};
+
+use serde::{Deserialize, Serialize};
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fs;
+
+/// The totals per assignee of a report, saved with `report --save` so a
+/// later report can show what changed with `report --compare`.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct Snapshot {
+    /// When the snapshot was taken, in RFC 3339.
+    pub taken: String,
+    pub assignees: BTreeMap<String, Totals>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct Totals {
+    pub issues: u32,
+    pub points: f64,
+    /// Seconds.
+    pub estimated: u64,
+    /// Seconds.
+    pub remaining: u64,
+    /// Seconds.
+    pub spent: u64,
+}
+
+impl From<&User> for Totals {
+    fn from(user: &User) -> Self {
+        Self {
+            issues: user.assignments(),
+            points: user.points(),
+            estimated: user.original_estimate(),
+            remaining: user.remaining_estimate(),
+            spent: user.time_spent(),
+        }
+    }
+}
+
+impl Snapshot {
+    pub fn load(path: &str) -> Result<Self> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: &str) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Formats the change of a count, like `+2` or `-0.5`. Rounded to a single
+/// decimal, so story points like 3.3 - 3.0 don't show float noise.
+pub fn count_delta(now: f64, then: f64) -> String {
+    let delta = ((now - then) * 10.0).round() / 10.0;
+    match delta == 0.0 {
+        true => "0".to_owned(),
+        false => format!("{:+}", delta),
+    }
+}
+
+/// Formats the change of a duration, like `+1.5d` or `-4.0h`.
+pub fn duration_delta(now: u64, then: u64, durations: &Durations) -> String {
+    match now.cmp(&then) {
+        Ordering::Equal => "0".to_owned(),
+        Ordering::Greater => format!("+{}", durations.display(now - then)),
+        Ordering::Less => format!("-{}", durations.display(then - now)),
+    }
+}
+
+/// The work committed to and completed in a sprint when a report was run,
+/// recorded in the history with `history = true` in the config file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SprintMetrics {
+    pub sprint_id: u64,
+    pub name: String,
+    /// Seconds.
+    pub committed: u64,
+    /// Seconds.
+    pub completed: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_deltas_are_rounded() {
+        assert_eq!(count_delta(3.3, 3.0), "+0.3");
+        assert_eq!(count_delta(1.0, 3.0), "-2");
+        assert_eq!(count_delta(2.0, 2.0), "0");
+        assert_eq!(count_delta(0.1 + 0.2, 0.3), "0");
+    }
+}
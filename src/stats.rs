@@ -0,0 +1,167 @@
+use crate::client::DEFAULT_TABLE_FORMAT;
+use crate::Result;
+
+use chrono::{DateTime, Local, TimeZone, Utc};
+use prettytable::{row, Row, Table};
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Commands that were run, kept locally when `stats = true` is set in the
+/// config file. Only the command names are kept, never their arguments.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct Stats {
+    pub runs: Vec<Run>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Run {
+    /// The command and its subcommands, e.g. `report matrix`.
+    pub command: String,
+    /// Seconds since the epoch.
+    pub at: i64,
+    pub ok: bool,
+}
+
+/// How often a command was run, and when it was run first and last.
+struct Usage {
+    runs: u32,
+    failed: u32,
+    first: i64,
+    last: i64,
+}
+
+impl Stats {
+    pub fn load() -> Result<Self> {
+        let path = match Self::path() {
+            Some(path) if path.exists() => path,
+            _ => return Ok(Default::default()),
+        };
+
+        // Like the cache, the stats are not worth failing a command for.
+        Ok(toml::from_str(&fs::read_to_string(path)?).unwrap_or_default())
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(path) = Self::path() {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(path, toml::to_string(self)?)?;
+        }
+        Ok(())
+    }
+
+    pub fn clear() -> Result<()> {
+        match Self::path() {
+            Some(path) if path.exists() => Ok(fs::remove_file(path)?),
+            _ => Ok(()),
+        }
+    }
+
+    /// Adds a run of a command and saves the stats.
+    pub fn record(command: &str, ok: bool) -> Result<()> {
+        let mut stats = Self::load()?;
+        stats.runs.push(Run {
+            command: command.to_owned(),
+            at: Utc::now().timestamp(),
+            ok,
+        });
+        stats.save()
+    }
+
+    pub fn path() -> Option<PathBuf> {
+        dirs::data_dir().map(|v| v.join("jira").join("stats.toml"))
+    }
+
+    /// Summarizes the runs per command since a point in time, most used first.
+    pub fn table(&self, since: Option<DateTime<Utc>>) -> (Row, Table) {
+        let since = since.map(|v| v.timestamp()).unwrap_or(i64::MIN);
+
+        let mut commands: BTreeMap<&str, Usage> = BTreeMap::new();
+        for run in self.runs.iter().filter(|v| v.at >= since) {
+            let usage = commands.entry(run.command.as_str()).or_insert(Usage {
+                runs: 0,
+                failed: 0,
+                first: run.at,
+                last: run.at,
+            });
+            usage.runs += 1;
+            if !run.ok {
+                usage.failed += 1;
+            }
+            usage.first = usage.first.min(run.at);
+            usage.last = usage.last.max(run.at);
+        }
+
+        let mut commands: Vec<(&str, Usage)> = commands.into_iter().collect();
+        commands.sort_by_key(|(_, usage)| std::cmp::Reverse(usage.runs));
+
+        let date = |at: i64| {
+            Local
+                .timestamp_opt(at, 0)
+                .single()
+                .map(|v| v.format("%F %R").to_string())
+                .unwrap_or_default()
+        };
+
+        let mut table = Table::new();
+        table.set_format(*DEFAULT_TABLE_FORMAT);
+        let titles = row!["Command", "Runs", "Failed", "First Run", "Last Run"];
+
+        for (command, usage) in commands {
+            table.add_row(row![
+                command,
+                usage.runs,
+                usage.failed,
+                date(usage.first),
+                date(usage.last)
+            ]);
+        }
+
+        (titles, table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_are_summarized_per_command() {
+        let run = |command: &str, at: i64, ok: bool| Run {
+            command: command.to_owned(),
+            at,
+            ok,
+        };
+        let stats = Stats {
+            runs: vec![
+                run("issues", 100, true),
+                run("report matrix", 200, true),
+                run("report matrix", 300, false),
+                run("report matrix", 400, true),
+                run("issues", 500, true),
+                run("sprints", 10, true),
+            ],
+        };
+
+        let (_, table) = stats.table(Utc.timestamp_opt(100, 0).single());
+
+        let content = |row: usize, cell: usize| {
+            table
+                .get_row(row)
+                .and_then(|v| v.get_cell(cell))
+                .map(|v| v.get_content())
+                .unwrap_or_default()
+        };
+        assert_eq!(table.len(), 2);
+        assert_eq!(content(0, 0), "report matrix");
+        assert_eq!(content(0, 1), "3");
+        assert_eq!(content(0, 2), "1");
+        assert_eq!(content(1, 0), "issues");
+        assert_eq!(content(1, 1), "2");
+    }
+}
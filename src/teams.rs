@@ -0,0 +1,60 @@
+use crate::Result;
+
+use prettytable::{Row, Table};
+use reqwest::blocking::Client;
+use serde_json::{json, Value};
+
+/// Builds an Adaptive Card message for a Teams incoming webhook, with a row
+/// of columns per table row since not all Teams clients support tables yet.
+pub fn card(title: &str, titles: &Row, table: &Table) -> Value {
+    let mut body = vec![json!({
+        "type": "TextBlock",
+        "text": title,
+        "size": "Medium",
+        "weight": "Bolder",
+        "wrap": true,
+    })];
+
+    body.push(columns(titles, true));
+    for row in table.row_iter() {
+        body.push(columns(row, false));
+    }
+
+    json!({
+        "type": "message",
+        "attachments": [{
+            "contentType": "application/vnd.microsoft.card.adaptive",
+            "content": {
+                "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                "type": "AdaptiveCard",
+                "version": "1.2",
+                "body": body,
+            },
+        }],
+    })
+}
+
+pub fn post(client: &Client, webhook: &str, card: &Value) -> Result<()> {
+    client.post(webhook).json(card).send()?.error_for_status()?;
+    Ok(())
+}
+
+fn columns(row: &Row, header: bool) -> Value {
+    let columns: Vec<Value> = row
+        .iter()
+        .map(|cell| {
+            json!({
+                "type": "Column",
+                "width": "stretch",
+                "items": [{
+                    "type": "TextBlock",
+                    "text": cell.get_content(),
+                    "weight": if header { "Bolder" } else { "Default" },
+                    "wrap": true,
+                }],
+            })
+        })
+        .collect();
+
+    json!({ "type": "ColumnSet", "separator": header, "columns": columns })
+}
@@ -0,0 +1,210 @@
+use crate::api::{Author, Worklog};
+use crate::{Error, Result};
+
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone};
+use chrono_tz::Tz;
+use reqwest::blocking::{Client, RequestBuilder};
+use serde::Deserialize;
+use serde_json::json;
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+const TEMPO_URL: &str = "https://api.tempo.io/4";
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct TempoWorklog {
+    tempo_worklog_id: u64,
+    time_spent_seconds: u64,
+    start_date: String,
+    start_time: Option<String>,
+    description: Option<String>,
+    author: TempoAuthor,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct TempoAuthor {
+    account_id: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct Page {
+    metadata: Metadata,
+    results: Vec<TempoWorklog>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Metadata {
+    next: Option<String>,
+}
+
+/// Reads and writes worklogs through the Tempo API, for instances where
+/// Jira's own worklogs are disabled in favor of Tempo.
+pub struct Tempo {
+    url: String,
+    token: String,
+    client: Client,
+    names: RefCell<BTreeMap<String, String>>,
+}
+
+impl Tempo {
+    pub fn new(token: &str, client: Client) -> Self {
+        Self::with_url(TEMPO_URL, token, client)
+    }
+
+    pub fn with_url(url: &str, token: &str, client: Client) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_owned(),
+            token: token.to_owned(),
+            client,
+            names: RefCell::new(BTreeMap::new()),
+        }
+    }
+
+    /// Returns the worklogs of an issue. Tempo only returns the account ID of
+    /// authors, so their names are looked up with `name` (once per account).
+    pub fn worklogs<F>(&self, issue_id: &str, tz: &Tz, name: F) -> Result<Vec<Worklog>>
+    where
+        F: Fn(&str) -> Result<String>,
+    {
+        let mut worklogs = Vec::new();
+        let mut url = format!("{}/worklogs/issue/{}", self.url, issue_id);
+        loop {
+            let page: Page = self.send(self.client.get(&url))?;
+            for worklog in page.results {
+                let account_id = worklog.author.account_id;
+                if !self.names.borrow().contains_key(&account_id) {
+                    let display_name = name(&account_id)?;
+                    self.names
+                        .borrow_mut()
+                        .insert(account_id.clone(), display_name);
+                }
+
+                let date = match NaiveDate::parse_from_str(&worklog.start_date, "%F") {
+                    Ok(date) => date,
+                    Err(_) => continue,
+                };
+                let time = worklog
+                    .start_time
+                    .and_then(|v| NaiveTime::parse_from_str(&v, "%T").ok())
+                    .unwrap_or_default();
+                let started = date.and_time(time);
+                let started = tz
+                    .from_local_datetime(&started)
+                    .earliest()
+                    .unwrap_or_else(|| tz.from_utc_datetime(&started));
+
+                worklogs.push(Worklog {
+                    id: worklog.tempo_worklog_id.to_string(),
+                    author: Some(Author {
+                        account_id: Some(account_id.clone()),
+                        display_name: self.names.borrow()[&account_id].clone(),
+                    }),
+                    started: started.format("%Y-%m-%dT%H:%M:%S%.3f%z").to_string(),
+                    time_spent_seconds: worklog.time_spent_seconds,
+                    comment: worklog.description,
+                });
+            }
+
+            match page.metadata.next {
+                Some(next) => url = next,
+                None => return Ok(worklogs),
+            }
+        }
+    }
+
+    /// Logs time on an issue, with the start in the local time of the author.
+    pub fn add_worklog(
+        &self,
+        issue_id: &str,
+        account_id: &str,
+        started: DateTime<Tz>,
+        seconds: u64,
+    ) -> Result<()> {
+        let body = json!({
+            "issueId": issue_id.parse::<u64>().unwrap_or_default(),
+            "authorAccountId": account_id,
+            "startDate": started.format("%F").to_string(),
+            "startTime": started.format("%T").to_string(),
+            "timeSpentSeconds": seconds,
+        });
+        let request = self.client.post(&format!("{}/worklogs", self.url));
+        self.send::<serde_json::Value>(request.json(&body))?;
+        Ok(())
+    }
+
+    fn send<T: serde::de::DeserializeOwned>(&self, request: RequestBuilder) -> Result<T> {
+        let response = request
+            .bearer_auth(&self.token)
+            .send()
+            .map_err(|err| Error::Tempo(err.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body: serde_json::Value = response.json().unwrap_or_default();
+            let message = body["errors"][0]["message"]
+                .as_str()
+                .unwrap_or_else(|| status.as_str());
+            return Err(Error::Tempo(message.to_owned()));
+        }
+
+        response.json().map_err(|err| Error::Tempo(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use httpmock::prelude::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn worklogs_are_read_in_the_instance_time_zone() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/worklogs/issue/10001")
+                .header("Authorization", "Bearer secret");
+            then.status(200).json_body(json!({
+                "metadata": { "count": 2 },
+                "results": [
+                    {
+                        "tempoWorklogId": 1,
+                        "timeSpentSeconds": 3600,
+                        "startDate": "2020-06-01",
+                        "startTime": "09:00:00",
+                        "description": "Pairing",
+                        "author": { "accountId": "id-jane.doe" },
+                    },
+                    {
+                        "tempoWorklogId": 2,
+                        "timeSpentSeconds": 1800,
+                        "startDate": "2020-06-02",
+                        "author": { "accountId": "id-jane.doe" },
+                    },
+                ],
+            }));
+        });
+
+        let lookups = Cell::new(0);
+        let tempo = Tempo::with_url(&server.base_url(), "secret", Client::new());
+        let worklogs = tempo
+            .worklogs("10001", &chrono_tz::Europe::Amsterdam, |_| {
+                lookups.set(lookups.get() + 1);
+                Ok("Jane Doe".to_owned())
+            })
+            .unwrap();
+
+        assert_eq!(lookups.get(), 1);
+        assert_eq!(worklogs.len(), 2);
+        assert_eq!(worklogs[0].started, "2020-06-01T09:00:00.000+0200");
+        assert_eq!(
+            worklogs[0].author.as_ref().unwrap().display_name,
+            "Jane Doe"
+        );
+        assert_eq!(worklogs[1].started, "2020-06-02T00:00:00.000+0200");
+    }
+}
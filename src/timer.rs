@@ -0,0 +1,105 @@
+use crate::Result;
+
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+use std::fs;
+use std::path::PathBuf;
+
+/// A running work timer, kept in a state file between invocations.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Timer {
+    pub issue: String,
+    /// Seconds since the epoch.
+    pub started: i64,
+}
+
+impl Timer {
+    pub fn load() -> Result<Option<Self>> {
+        match Self::path() {
+            Some(path) if path.exists() => Ok(Some(toml::from_str(&fs::read_to_string(path)?)?)),
+            _ => Ok(None),
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(path) = Self::path() {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(path, toml::to_string(self)?)?;
+        }
+        Ok(())
+    }
+
+    pub fn clear() -> Result<()> {
+        match Self::path() {
+            Some(path) if path.exists() => Ok(fs::remove_file(path)?),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn started_at(&self) -> DateTime<Utc> {
+        Utc.timestamp_opt(self.started, 0)
+            .single()
+            .unwrap_or_else(Utc::now)
+    }
+
+    pub fn path() -> Option<PathBuf> {
+        dirs::data_dir().map(|v| v.join("jira").join("timer.toml"))
+    }
+}
+
+/// Parses a rounding interval like `15m` or `1h` into seconds.
+pub fn interval(input: &str) -> Option<i64> {
+    let (number, unit) = match (input.strip_suffix('m'), input.strip_suffix('h')) {
+        (Some(number), _) => (number, 60),
+        (None, Some(number)) => (number, 60 * 60),
+        (None, None) => return None,
+    };
+    number
+        .parse::<i64>()
+        .ok()
+        .filter(|v| *v > 0)
+        .map(|v| v * unit)
+}
+
+/// Rounds seconds to the nearest multiple of an interval, but never below a
+/// single interval so short sessions are still logged.
+pub fn round(seconds: i64, interval: i64) -> i64 {
+    let rounded = (seconds + interval / 2) / interval * interval;
+    rounded.max(interval)
+}
+
+/// Formats seconds as hours and minutes, e.g. `1h 15m`.
+pub fn hours(seconds: i64) -> String {
+    let minutes = seconds / 60;
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{}m", minutes),
+        (hours, 0) => format!("{}h", hours),
+        (hours, minutes) => format!("{}h {}m", hours, minutes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elapsed_time_is_rounded_and_formatted() {
+        assert_eq!(interval("15m"), Some(900));
+        assert_eq!(interval("1h"), Some(3600));
+        assert_eq!(interval("0m"), None);
+        assert_eq!(interval("15"), None);
+        assert_eq!(interval("5é"), None);
+        assert_eq!(interval(""), None);
+
+        assert_eq!(round(4 * 60, 900), 900);
+        assert_eq!(round(23 * 60, 900), 1800);
+        assert_eq!(round(80 * 60, 900), 4500);
+
+        assert_eq!(hours(4500), "1h 15m");
+        assert_eq!(hours(7200), "2h");
+        assert_eq!(hours(600), "10m");
+    }
+}
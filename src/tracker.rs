@@ -0,0 +1,217 @@
+use crate::duration::Durations;
+use crate::{Error, Result};
+
+use serde::Deserialize;
+
+use std::collections::BTreeMap;
+use std::io;
+
+/// Describes how the columns of a CSV export (from Trello, Asana, Linear or
+/// any other tracker) map onto the fields of a Jira issue.
+#[derive(Deserialize, Debug)]
+pub struct Mapping {
+    pub project: String,
+    #[serde(default = "default_issue_type")]
+    pub issue_type: String,
+    pub columns: Columns,
+    /// Unit of estimates that are plain numbers: `m`, `h` or `d`.
+    #[serde(default = "default_estimate_unit")]
+    pub estimate_unit: String,
+    /// Maps names used in the export to Jira users (name, email or account ID).
+    #[serde(default)]
+    pub assignees: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Columns {
+    pub summary: String,
+    pub id: Option<String>,
+    pub description: Option<String>,
+    pub estimate: Option<String>,
+    pub assignee: Option<String>,
+    /// Column referring to the parent task, by ID or by summary.
+    pub parent: Option<String>,
+}
+
+/// A single task read from the export.
+#[derive(Debug, PartialEq)]
+pub struct Task {
+    pub id: String,
+    pub summary: String,
+    pub description: Option<String>,
+    pub estimate: Option<String>,
+    pub assignee: Option<String>,
+    pub parent: Option<String>,
+}
+
+fn default_issue_type() -> String {
+    "Story".to_owned()
+}
+
+fn default_estimate_unit() -> String {
+    "h".to_owned()
+}
+
+/// Reads the tasks of a CSV export. Tasks without an ID column are
+/// identified by their summary, and parent references are checked so
+/// nothing gets created when the export is incomplete.
+pub fn tasks<R: io::Read>(
+    reader: R,
+    mapping: &Mapping,
+    durations: &Durations,
+) -> Result<Vec<Task>> {
+    let mut reader = csv::Reader::from_reader(reader);
+    let headers = reader.headers()?.clone();
+
+    let index = |name: Option<&String>| -> Result<Option<usize>> {
+        match name {
+            Some(name) => headers
+                .iter()
+                .position(|v| v.trim() == name)
+                .map(Some)
+                .ok_or_else(|| Error::Import(format!("no column named `{}`", name))),
+            None => Ok(None),
+        }
+    };
+    let columns = &mapping.columns;
+    let (summary, id, description, estimate, assignee, parent) = (
+        index(Some(&columns.summary))?,
+        index(columns.id.as_ref())?,
+        index(columns.description.as_ref())?,
+        index(columns.estimate.as_ref())?,
+        index(columns.assignee.as_ref())?,
+        index(columns.parent.as_ref())?,
+    );
+
+    let mut tasks = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        let value = |index: Option<usize>| {
+            index
+                .and_then(|i| record.get(i))
+                .map(|v| v.trim())
+                .filter(|v| !v.is_empty())
+                .map(|v| v.to_owned())
+        };
+
+        let summary = match value(summary) {
+            Some(summary) => summary,
+            None => continue,
+        };
+        tasks.push(Task {
+            id: value(id).unwrap_or_else(|| summary.clone()),
+            summary,
+            description: value(description),
+            estimate: value(estimate)
+                .map(|v| self::estimate(&v, &mapping.estimate_unit, durations)),
+            assignee: value(assignee).map(|v| mapping.assignees.get(&v).cloned().unwrap_or(v)),
+            parent: value(parent),
+        });
+    }
+
+    // Point parent references made by summary at the ID of the parent.
+    let mut parents = Vec::new();
+    for task in tasks.iter() {
+        let parent = match &task.parent {
+            Some(parent) => parent,
+            None => {
+                parents.push(None);
+                continue;
+            }
+        };
+        match tasks
+            .iter()
+            .find(|v| v.id == *parent)
+            .or_else(|| tasks.iter().find(|v| v.summary == *parent))
+        {
+            Some(v) if v.parent.is_some() => {
+                return Err(Error::Import(format!(
+                    "`{}` is nested more than one level deep",
+                    task.summary
+                )))
+            }
+            Some(v) => parents.push(Some(v.id.clone())),
+            None => {
+                return Err(Error::Import(format!(
+                    "parent `{}` of `{}` is not in the export",
+                    parent, task.summary
+                )))
+            }
+        }
+    }
+    for (task, parent) in tasks.iter_mut().zip(parents) {
+        task.parent = parent;
+    }
+
+    Ok(tasks)
+}
+
+/// Turns plain numbers into a Jira duration using the configured unit, and
+/// passes anything else (like `2h 30m`) on as is. Fractions are converted to
+/// minutes, using the length of a working day.
+fn estimate(input: &str, unit: &str, durations: &Durations) -> String {
+    let number = match input.parse::<f64>() {
+        Ok(number) => number,
+        Err(_) => return input.to_owned(),
+    };
+    if number.fract() == 0.0 {
+        return format!("{}{}", number, unit);
+    }
+    let minutes = match unit {
+        "d" => number * (durations.day() / 60) as f64,
+        "h" => number * 60.0,
+        _ => number,
+    };
+    format!("{}m", minutes.round())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping() -> Mapping {
+        serde_yaml::from_str(
+            "project: ABC\n\
+             columns:\n  \
+               id: ID\n  \
+               summary: Title\n  \
+               estimate: Estimate\n  \
+               assignee: Assignee\n  \
+               parent: Parent issue\n\
+             assignees:\n  \
+               jane: jane@example.com\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn tasks_are_read_from_an_export() {
+        let export = "ID,Title,Estimate,Assignee,Parent issue\n\
+                      LIN-1,Migrate the database,,jane,\n\
+                      LIN-2,Write the migration,1.5,jane,LIN-1\n\
+                      LIN-3,Run the migration,2h 30m,,Migrate the database\n\
+                      LIN-4,,3,,\n";
+
+        let tasks = tasks(export.as_bytes(), &mapping(), &Durations::default()).unwrap();
+
+        assert_eq!(tasks.len(), 3);
+        assert_eq!(tasks[0].assignee.as_deref(), Some("jane@example.com"));
+        assert_eq!(tasks[0].parent, None);
+        assert_eq!(tasks[1].estimate.as_deref(), Some("90m"));
+        assert_eq!(tasks[1].parent.as_deref(), Some("LIN-1"));
+        assert_eq!(tasks[2].estimate.as_deref(), Some("2h 30m"));
+        assert_eq!(tasks[2].parent.as_deref(), Some("LIN-1"));
+        assert_eq!(tasks[2].assignee, None);
+    }
+
+    #[test]
+    fn missing_parents_are_rejected() {
+        let export = "ID,Title,Estimate,Assignee,Parent issue\n\
+                      LIN-2,Write the migration,1,,LIN-1\n";
+
+        assert!(matches!(
+            tasks(export.as_bytes(), &mapping(), &Durations::default()),
+            Err(Error::Import(_))
+        ));
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 #[derive(Debug, Default)]
 pub struct User {
@@ -6,6 +6,8 @@ pub struct User {
     estimate: f64,
     remaining: f64,
     actual: f64,
+    points: f64,
+    sources: BTreeSet<String>,
 }
 
 impl User {
@@ -19,24 +21,42 @@ impl User {
         self.issues
     }
 
-    pub fn original_estimate_days(&self) -> f64 {
-        self.estimate / 60.0 / 60.0 / 8.0
+    pub fn original_estimate(&self) -> u64 {
+        self.estimate.round() as u64
     }
 
-    pub fn remaining_estimate_days(&self) -> f64 {
-        self.remaining / 60.0 / 60.0 / 8.0
+    pub fn remaining_estimate(&self) -> u64 {
+        self.remaining.round() as u64
     }
 
-    pub fn time_spent_days(&self) -> f64 {
-        self.actual / 60.0 / 60.0 / 8.0
+    pub fn time_spent(&self) -> u64 {
+        self.actual.round() as u64
+    }
+
+    pub fn points(&self) -> f64 {
+        self.points
+    }
+
+    pub fn sources(&self) -> Vec<String> {
+        self.sources.iter().cloned().collect()
     }
 }
 
-pub struct Users(BTreeMap<String, User>);
+pub struct Users {
+    users: BTreeMap<String, User>,
+    source: Option<String>,
+}
 
 impl Users {
     pub fn new() -> Self {
-        Self(BTreeMap::new())
+        Self {
+            users: BTreeMap::new(),
+            source: None,
+        }
+    }
+
+    pub fn source(&mut self, source: &str) {
+        self.source = Some(source.to_owned());
     }
 
     pub fn original_estimate_seconds(
@@ -45,7 +65,7 @@ impl Users {
         estimate: Option<u64>,
     ) -> Option<u64> {
         if let Some(estimate) = estimate {
-            let user = self.0.entry(assignee).or_insert(User::new());
+            let user = self.user(assignee);
             user.issues += 1;
             user.estimate += estimate as f64;
         }
@@ -58,7 +78,7 @@ impl Users {
         remaining: Option<u64>,
     ) -> Option<u64> {
         if let Some(remaining) = remaining {
-            let user = self.0.entry(assignee).or_insert(User::new());
+            let user = self.user(assignee);
             user.remaining += remaining as f64;
         }
         remaining
@@ -66,20 +86,36 @@ impl Users {
 
     pub fn time_spent_seconds(&mut self, assignee: String, actual: Option<u64>) -> Option<u64> {
         if let Some(actual) = actual {
-            let user = self.0.entry(assignee).or_insert(User::new());
+            let user = self.user(assignee);
             user.actual += actual as f64;
         }
         actual
     }
+
+    pub fn points(&mut self, assignee: String, points: Option<f64>) -> Option<f64> {
+        if let Some(points) = points {
+            let user = self.user(assignee);
+            user.points += points;
+        }
+        points
+    }
+
+    fn user(&mut self, assignee: String) -> &mut User {
+        let user = self.users.entry(assignee).or_default();
+        if let Some(source) = &self.source {
+            user.sources.insert(source.clone());
+        }
+        user
+    }
 }
 
 impl Iterator for Users {
     type Item = (String, User);
 
     fn next(&mut self) -> Option<(String, User)> {
-        if let Some(k) = self.0.keys().next() {
+        if let Some(k) = self.users.keys().next() {
             let k = k.clone();
-            if let Some(v) = self.0.remove(&k) {
+            if let Some(v) = self.users.remove(&k) {
                 return Some((k, v));
             }
         }
@@ -87,3 +123,43 @@ impl Iterator for Users {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aggregates_per_assignee() {
+        let mut users = Users::new();
+        users.source("Sprint 1");
+        users.original_estimate_seconds("Jane".to_owned(), Some(28800));
+        users.original_estimate_seconds("Jane".to_owned(), Some(14400));
+        users.remaining_estimate_seconds("Jane".to_owned(), Some(14400));
+        users.source("Sprint 2");
+        users.time_spent_seconds("Jane".to_owned(), Some(57600));
+        users.original_estimate_seconds("Bob".to_owned(), None);
+
+        let users: Vec<(String, User)> = users.collect();
+        assert_eq!(users.len(), 1);
+
+        let (name, user) = &users[0];
+        assert_eq!(name, "Jane");
+        assert_eq!(user.assignments(), 2);
+        assert_eq!(user.original_estimate(), 43200);
+        assert_eq!(user.remaining_estimate(), 14400);
+        assert_eq!(user.time_spent(), 57600);
+        assert_eq!(user.sources(), vec!["Sprint 1", "Sprint 2"]);
+    }
+
+    #[test]
+    fn iterates_in_assignee_order() {
+        let mut users = Users::new();
+        for name in &["Zoe", "Adam", "Mia"] {
+            users.remaining_estimate_seconds(name.to_string(), Some(1));
+        }
+
+        assert_eq!(
+            users.map(|(name, _)| name).collect::<Vec<String>>(),
+            vec!["Adam", "Mia", "Zoe"]
+        );
+    }
+}
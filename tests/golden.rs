@@ -0,0 +1,305 @@
+use httpmock::prelude::*;
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn fixture(name: &str) -> String {
+    fs::read_to_string(Path::new("tests/fixtures").join(format!("{}.json", name))).unwrap()
+}
+
+fn server() -> MockServer {
+    let server = MockServer::start();
+
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/agile/latest/board");
+        then.status(200).body(fixture("boards"));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/agile/latest/board/1");
+        then.status(200).body(fixture("board-1"));
+    });
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/rest/agile/latest/board/1/configuration");
+        then.status(200).body(fixture("board-1-configuration"));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/agile/latest/board/1/sprint");
+        then.status(200).body(fixture("sprints"));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/agile/latest/sprint/10");
+        then.status(200).body(fixture("sprint-10"));
+    });
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/rest/api/latest/user/search")
+            .query_param("query", "jane");
+        then.status(200).body(fixture("users"));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/latest/myself");
+        then.status(200).body(fixture("myself"));
+    });
+
+    server
+}
+
+fn issues(server: &MockServer, jql: &str) {
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/rest/agile/latest/board/1/issue")
+            .query_param("jql", jql);
+        then.status(200).body(fixture("issues"));
+    });
+}
+
+fn run(server: &MockServer, args: &[&str]) -> Output {
+    let tmp = Path::new(env!("CARGO_TARGET_TMPDIR")).join("golden");
+
+    Command::new(env!("CARGO_BIN_EXE_jira"))
+        .args(args)
+        .env_remove("JIRA_ORGANIZATION")
+        .env("JIRA_HOST", server.base_url())
+        .env("JIRA_USER", "jane@example.com")
+        .env("JIRA_TOKEN", "secret")
+        .env("JIRA_CONFIG", tmp.join("config.toml"))
+        .env("XDG_CACHE_HOME", &tmp)
+        .env("HOME", &tmp)
+        .output()
+        .unwrap()
+}
+
+fn jira(server: &MockServer, args: &[&str]) -> String {
+    let output = run(server, args);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    // Issue URLs contain the random port of the mock server.
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .replace(&server.base_url(), "https://example.atlassian.net")
+}
+
+/// Compares the output with `tests/golden/<name>.txt`, or rewrites that file
+/// when running with `UPDATE_GOLDEN=1`.
+fn assert_golden(name: &str, output: &str) {
+    let path = Path::new("tests/golden").join(format!("{}.txt", name));
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&path, output).unwrap();
+    }
+    assert_eq!(fs::read_to_string(&path).unwrap(), output, "{:?}", path);
+}
+
+#[test]
+fn boards() {
+    let server = server();
+    assert_golden("boards", &jira(&server, &["boards"]));
+}
+
+#[test]
+fn boards_view() {
+    let server = server();
+    issues(&server, "sprint in openSprints() ORDER BY rank");
+    assert_golden("boards-view", &jira(&server, &["boards", "view", "1"]));
+}
+
+#[test]
+fn sprints() {
+    let server = server();
+    assert_golden("sprints", &jira(&server, &["sprints", "-b", "1"]));
+}
+
+#[test]
+fn sprints_quiet() {
+    let server = server();
+    assert_eq!(jira(&server, &["sprints", "-b", "1", "-q"]), "11\n10\n");
+}
+
+#[test]
+fn sprints_with_instance_tz() {
+    let server = server();
+    assert_golden(
+        "sprints-instance-tz",
+        &jira(
+            &server,
+            &["sprints", "-b", "1", "--instance-tz", "America/New_York"],
+        ),
+    );
+}
+
+#[test]
+fn issues_for_a_sprint() {
+    let server = server();
+    issues(
+        &server,
+        "status not in (10001) AND sprint=10 ORDER BY issuekey",
+    );
+    assert_golden("issues", &jira(&server, &["issues", "-s", "10"]));
+}
+
+#[test]
+fn issues_fit_in_max_width() {
+    let server = server();
+    issues(
+        &server,
+        "status not in (10001) AND sprint=10 ORDER BY issuekey",
+    );
+    assert_golden(
+        "issues-max-width",
+        &jira(&server, &["issues", "-s", "10", "--max-width", "140"]),
+    );
+}
+
+#[test]
+fn issues_show() {
+    let server = server();
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/latest/issue/ABC-1");
+        then.status(200).body(fixture("issue-abc-1"));
+    });
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/rest/api/latest/issue/ABC-1/remotelink");
+        then.status(200).body(fixture("remotelinks"));
+    });
+    assert_golden("issues-show", &jira(&server, &["issues", "show", "ABC-1"]));
+}
+
+#[test]
+fn report_for_a_sprint() {
+    let server = server();
+    issues(&server, "sprint=10 ORDER BY assignee");
+    assert_golden("report", &jira(&server, &["report", "-s", "10"]));
+}
+
+#[test]
+fn report_for_planning() {
+    let server = server();
+    issues(
+        &server,
+        "statusCategory!=Done AND sprint=10 ORDER BY assignee",
+    );
+    assert_golden(
+        "report-planning",
+        &jira(&server, &["report", "-s", "10", "--planning"]),
+    );
+}
+
+#[test]
+fn report_with_a11y() {
+    let server = server();
+    issues(&server, "sprint=10 ORDER BY assignee");
+    assert_golden(
+        "report-a11y",
+        &jira(&server, &["report", "-s", "10", "--a11y"]),
+    );
+}
+
+#[test]
+fn publish_updates_an_existing_page() {
+    let server = server();
+    issues(&server, "sprint=10 ORDER BY assignee");
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/wiki/rest/api/content")
+            .query_param("spaceKey", "TEAM")
+            .query_param("title", "Sprint report: ABC Sprint 10");
+        then.status(200).body(fixture("confluence-pages"));
+    });
+    server.mock(|when, then| {
+        when.method(PUT)
+            .path("/wiki/rest/api/content/123")
+            .json_body_partial(r#"{ "version": { "number": 4 } }"#);
+        then.status(200).body(fixture("confluence-page"));
+    });
+    assert_golden(
+        "publish",
+        &jira(&server, &["publish", "-s", "10", "--space", "TEAM"]),
+    );
+}
+
+#[test]
+fn errors_are_json_with_json_output() {
+    let server = server();
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/agile/latest/sprint/11");
+        then.status(429)
+            .body(r#"{ "errorMessages": ["Too many requests"], "errors": {} }"#);
+    });
+
+    let output = run(&server, &["issues", "-s", "11", "--output", "json"]);
+    assert!(!output.status.success());
+    let error: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(error["error"]["kind"], "RateLimited");
+    assert_eq!(error["error"]["status"], 429);
+}
+
+#[test]
+fn timings_are_printed_to_stderr() {
+    let server = server();
+    issues(
+        &server,
+        "status not in (10001) AND sprint=10 ORDER BY issuekey",
+    );
+
+    let output = run(&server, &["issues", "-s", "10", "--timings"]);
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    for step in &[
+        "client setup",
+        "board and sprint fetch",
+        "board configuration fetch",
+        "search ABC Sprint 10 (6 issues)",
+        "rendering",
+        "Total",
+    ] {
+        assert!(stderr.contains(step), "{:?} not in {}", step, stderr);
+    }
+}
+
+#[test]
+fn users_search() {
+    let server = server();
+    assert_golden("users-search", &jira(&server, &["users", "search", "jane"]));
+}
+
+#[test]
+fn whoami() {
+    let server = server();
+    assert_golden("whoami", &jira(&server, &["whoami"]));
+}
+
+#[test]
+fn automation_list() {
+    let server = server();
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/latest/project/ABC");
+        then.status(200).body(fixture("project"));
+    });
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/rest/cb-automation/latest/project/10000/rule");
+        then.status(200).body(fixture("automation-rules"));
+    });
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/rest/cb-automation/latest/audit/GLOBAL/item")
+            .query_param("ruleIdFilter", "1");
+        then.status(200).body(fixture("automation-audit"));
+    });
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/rest/cb-automation/latest/audit/GLOBAL/item")
+            .query_param("ruleIdFilter", "2");
+        then.status(200).body(fixture("automation-audit-empty"));
+    });
+    assert_golden(
+        "automation-list",
+        &jira(&server, &["automation", "list", "-p", "ABC"]),
+    );
+}